@@ -1,5 +1,9 @@
 pub mod analyzer;
 pub mod expr;
+pub mod float_value;
+pub mod function;
+pub mod global_variable;
 pub mod program;
 pub mod stmt;
+pub mod types;
 pub mod variable;
@@ -1,42 +1,265 @@
+mod decl;
+mod eval;
 mod expr;
+mod function;
+mod init;
 mod stmt;
 
-use std::collections::{BTreeMap, VecDeque};
+use std::{
+    collections::{BTreeMap, BTreeSet, VecDeque},
+    rc::Rc,
+};
 
-use crate::parse::row_program::RowProgram;
+use anyhow::anyhow;
 
-use super::{program::Program, stmt::Stmt, variable::Variable};
+use crate::{
+    file::position::Position,
+    parse::{
+        row_decl::RowStorageClass,
+        row_program::{RowExternalDecl, RowProgram},
+    },
+};
+
+use super::{
+    global_variable::GlobalVariable,
+    program::Program,
+    types::{align_to, StructType, Type},
+    variable::Variable,
+};
 
 pub struct Analyzer {
-    var: BTreeMap<String, Variable>,
+    scopes: Vec<Scope>,
+    func: BTreeMap<String, Type>,
+    globals: Vec<GlobalVariable>,
+    linkages: BTreeMap<String, Linkage>,
+    static_count: usize,
+    string_count: usize,
+    /// The return type of the function being analyzed.
+    ret: Type,
     offset: usize,
+    max_offset: usize,
+    loop_depth: usize,
+    switches: Vec<Switch>,
+    labels: BTreeSet<String>,
+    gotos: Vec<(String, Position)>,
+}
+
+/// The ordinary identifiers and the tags declared in one scope.
+#[derive(Debug, Default)]
+struct Scope {
+    symbols: BTreeMap<String, Symbol>,
+    tags: BTreeMap<String, Tag>,
+}
+
+/// An ordinary identifier visible in a scope.
+#[derive(Debug, Clone)]
+enum Symbol {
+    Variable { var: Variable },
+    EnumConst { value: i64 },
+    Typedef { ty: Type },
+}
+
+/// The linkage of a function or a file-scope variable.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Linkage {
+    External,
+    Internal,
+}
+
+/// The case labels collected for an enclosing `switch`.
+#[derive(Debug, Clone)]
+struct Switch {
+    ty: Type,
+    cases: Vec<i64>,
+    has_default: bool,
+}
+
+/// A struct, union or enum tag.
+#[derive(Debug, Clone)]
+enum Tag {
+    Struct { struct_type: Rc<StructType> },
+    Enum,
 }
 
 impl Analyzer {
     pub fn new() -> Self {
         Self {
-            var: BTreeMap::new(),
+            scopes: vec![Scope::default()],
+            func: BTreeMap::new(),
+            globals: Vec::new(),
+            linkages: BTreeMap::new(),
+            static_count: 0,
+            string_count: 0,
+            ret: Type::Void,
             offset: 0,
+            max_offset: 0,
+            loop_depth: 0,
+            switches: Vec::new(),
+            labels: BTreeSet::new(),
+            gotos: Vec::new(),
         }
     }
 
     pub fn analyze(&mut self, row_program: RowProgram) -> anyhow::Result<Program> {
-        let stmts = row_program
-            .stmts
-            .into_iter()
-            .map(|stmt| self.analyze_stmt(stmt))
-            .collect::<anyhow::Result<VecDeque<Stmt>>>()?;
-        Ok(Program::new(stmts, self.offset))
+        let mut functions = VecDeque::new();
+        for external_decl in row_program.external_decls.into_iter() {
+            match external_decl {
+                RowExternalDecl::Function { function } => {
+                    functions.push_back(self.analyze_function(*function)?);
+                }
+                RowExternalDecl::Decl { decl } => self.analyze_global_decl(decl)?,
+            }
+        }
+        Ok(Program::new(functions, std::mem::take(&mut self.globals)))
     }
 
-    fn get_var(&mut self, name: String) -> Variable {
-        if let Some(var) = self.var.get(&name) {
-            var.clone()
+    fn declare_var(
+        &mut self,
+        name: String,
+        ty: Type,
+        position: &Position,
+    ) -> anyhow::Result<Variable> {
+        self.offset = align_to(self.offset + ty.size(), ty.align());
+        self.max_offset = self.max_offset.max(self.offset);
+        let var = Variable::new_local(self.offset, ty);
+        let symbol = Symbol::Variable { var: var.clone() };
+        self.declare_symbol(name, symbol, position)?;
+        Ok(var)
+    }
+
+    /// Declares a variable with static storage, merging it with earlier declarations of the same
+    /// object.
+    fn declare_global_var(
+        &mut self,
+        name: String,
+        global: GlobalVariable,
+        position: &Position,
+    ) -> anyhow::Result<()> {
+        let var = if let Some(prev) = self
+            .globals
+            .iter_mut()
+            .find(|prev| prev.name == global.name)
+        {
+            if prev.ty != global.ty {
+                match (&prev.ty, &global.ty) {
+                    (Type::Array { ty, len: 0 }, Type::Array { ty: new_ty, .. })
+                        if ty == new_ty =>
+                    {
+                        prev.ty = global.ty;
+                    }
+                    (Type::Array { ty, .. }, Type::Array { ty: new_ty, len: 0 })
+                        if ty == new_ty => {}
+                    _ => return Err(anyhow!("{}Conflicting types for `{}`", position, name)),
+                }
+            }
+            if global.init.is_some() {
+                if prev.init.is_some() {
+                    return Err(anyhow!("{}Redefinition of `{}`", position, name));
+                }
+                prev.init = global.init;
+            }
+            prev.is_extern &= global.is_extern;
+            Variable::new_global(prev.name.clone(), prev.ty.clone())
         } else {
-            self.offset += 8;
-            let var = Variable::new(self.offset);
-            self.var.insert(name, var.clone());
+            let var = Variable::new_global(global.name.clone(), global.ty.clone());
+            self.globals.push(global);
             var
+        };
+        // Redeclaring the same object in one scope is allowed.
+        let scope = &mut self.scopes.last_mut().unwrap().symbols;
+        if let Some(Symbol::Variable { var: prev_var }) = scope.get(&name) {
+            if prev_var.label() == var.label() {
+                scope.insert(name, Symbol::Variable { var });
+                return Ok(());
+            }
+        }
+        self.declare_symbol(name, Symbol::Variable { var }, position)
+    }
+
+    /// Resolves the linkage of a name with static storage and checks it against earlier
+    /// declarations.
+    fn declare_linkage(
+        &mut self,
+        name: &str,
+        storage_class: &Option<RowStorageClass>,
+        is_func: bool,
+        position: &Position,
+    ) -> anyhow::Result<Linkage> {
+        let prev = self.linkages.get(name).copied();
+        let linkage = match storage_class {
+            Some(RowStorageClass::Static) => Linkage::Internal,
+            Some(RowStorageClass::Extern) => prev.unwrap_or(Linkage::External),
+            _ if is_func => prev.unwrap_or(Linkage::External),
+            _ => Linkage::External,
+        };
+        match (prev, linkage) {
+            (Some(Linkage::Internal), Linkage::External) => Err(anyhow!(
+                "{}Non-static declaration of `{}` follows static declaration",
+                position,
+                name
+            )),
+            (Some(Linkage::External), Linkage::Internal) => Err(anyhow!(
+                "{}Static declaration of `{}` follows non-static declaration",
+                position,
+                name
+            )),
+            _ => {
+                self.linkages.insert(name.to_string(), linkage);
+                Ok(linkage)
+            }
+        }
+    }
+
+    fn declare_symbol(
+        &mut self,
+        name: String,
+        symbol: Symbol,
+        position: &Position,
+    ) -> anyhow::Result<()> {
+        let scope = &mut self.scopes.last_mut().unwrap().symbols;
+        if scope.contains_key(&name) {
+            return Err(anyhow!("{}Redefinition of `{}`", position, name));
+        }
+        scope.insert(name, symbol);
+        Ok(())
+    }
+
+    fn declare_func(&mut self, name: String, ty: Type, position: &Position) -> anyhow::Result<()> {
+        if let Some(prev_ty) = self.func.get(&name) {
+            if !prev_ty.is_compatible(&ty) {
+                return Err(anyhow!("{}Conflicting types for `{}`", position, name));
+            }
+            if ty.is_unprototyped() {
+                return Ok(());
+            }
         }
+        self.func.insert(name, ty);
+        Ok(())
+    }
+
+    fn get_symbol(&self, name: &str, position: &Position) -> anyhow::Result<Symbol> {
+        self.scopes
+            .iter()
+            .rev()
+            .find_map(|scope| scope.symbols.get(name))
+            .cloned()
+            .ok_or_else(|| anyhow!("{}Undeclared identifier `{}`", position, name))
+    }
+
+    /// Looks up a tag. A definition only sees the tags of the innermost scope, so that it may
+    /// shadow an outer one.
+    fn get_tag(&self, name: &str, is_definition: bool) -> Option<Tag> {
+        if is_definition {
+            return self.scopes.last().unwrap().tags.get(name).cloned();
+        }
+        self.scopes
+            .iter()
+            .rev()
+            .find_map(|scope| scope.tags.get(name))
+            .cloned()
+    }
+
+    fn declare_tag(&mut self, name: String, tag: Tag) {
+        self.scopes.last_mut().unwrap().tags.insert(name, tag);
     }
 }
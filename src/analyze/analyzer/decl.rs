@@ -0,0 +1,511 @@
+use std::rc::Rc;
+
+use anyhow::anyhow;
+
+use crate::{
+    analyze::{
+        expr::{BinaryOpKind, Expr},
+        global_variable::{GlobalInit, GlobalVariable, Relocation},
+        stmt::Stmt,
+        types::{FloatKind, IntegerKind, StructType, Type},
+    },
+    file::position::Position,
+    parse::row_decl::{
+        RowDecl, RowDeclSpec, RowDeclarator, RowDerived, RowEnumerator, RowInitializer, RowMember,
+        RowStorageClass, RowTypeSpec,
+    },
+};
+
+use super::{Analyzer, Linkage, Symbol, Tag};
+
+impl Analyzer {
+    pub fn analyze_decl(&mut self, row_decl: RowDecl, position: Position) -> anyhow::Result<Stmt> {
+        if let Some(RowStorageClass::Typedef) = row_decl.decl_spec.storage_class {
+            self.analyze_typedef(row_decl)?;
+            return Ok(Stmt::new_cpd(Vec::new(), position));
+        }
+        let storage_class = row_decl.decl_spec.storage_class.clone();
+        let mut stmts = Vec::new();
+        let base = self.resolve_decl_spec(&row_decl.decl_spec)?;
+        for init_declarator in row_decl.init_declarators.into_iter() {
+            let declarator = init_declarator.declarator;
+            let ty = self.resolve_declarator(base.clone(), &declarator)?;
+            let name = Self::declarator_name(&declarator)?;
+            if let Type::Function { .. } = ty {
+                if let Some(RowStorageClass::Static | RowStorageClass::Auto) = storage_class {
+                    return Err(anyhow!(
+                        "{}Invalid storage class for function `{}`",
+                        declarator.position,
+                        name
+                    ));
+                }
+                self.declare_linkage(&name, &storage_class, true, &declarator.position)?;
+                self.declare_func(name, ty, &declarator.position)?;
+                continue;
+            }
+            let is_extern = matches!(storage_class, Some(RowStorageClass::Extern));
+            let is_sized_later = is_extern || init_declarator.init.is_some();
+            Self::check_var_type(&name, &ty, is_sized_later, &declarator.position)?;
+            let is_const = Self::is_const_object(&row_decl.decl_spec, &declarator);
+            match storage_class {
+                Some(RowStorageClass::Extern) => {
+                    if init_declarator.init.is_some() {
+                        return Err(anyhow!(
+                            "{}Block-scope extern variable `{}` has an initializer",
+                            declarator.position,
+                            name
+                        ));
+                    }
+                    let linkage =
+                        self.declare_linkage(&name, &storage_class, false, &declarator.position)?;
+                    let is_static = linkage == Linkage::Internal;
+                    let global =
+                        GlobalVariable::new(name.clone(), ty, None, is_const, is_static, true);
+                    self.declare_global_var(name, global, &declarator.position)?;
+                }
+                Some(RowStorageClass::Static) => {
+                    // Function-local statics get a label no C identifier can collide with.
+                    let label = format!("{}.{}", name, self.static_count);
+                    self.static_count += 1;
+                    let (ty, init) = match init_declarator.init {
+                        Some(row_init) => {
+                            let (ty, init) = self.analyze_global_init(ty, row_init)?;
+                            (ty, Some(init))
+                        }
+                        None => (ty, None),
+                    };
+                    let global = GlobalVariable::new(label, ty, init, is_const, true, false);
+                    self.declare_global_var(name, global, &declarator.position)?;
+                }
+                _ => {
+                    let Some(row_init) = init_declarator.init else {
+                        self.declare_var(name, ty, &declarator.position)?;
+                        continue;
+                    };
+                    let is_list = matches!(row_init, RowInitializer::List { .. });
+                    // The variable is in scope in its own initializer unless its length comes
+                    // from the initializer.
+                    let (var, elems) = if ty.size() == 0 {
+                        let (ty, elems) = self.analyze_initializer(ty, row_init)?;
+                        (self.declare_var(name, ty, &declarator.position)?, elems)
+                    } else {
+                        let var = self.declare_var(name, ty.clone(), &declarator.position)?;
+                        (var, self.analyze_initializer(ty, row_init)?.1)
+                    };
+                    if var.ty.is_array() || (is_list && var.ty.is_struct()) {
+                        stmts.push(Stmt::new_zero_fill(var.clone(), position.clone()));
+                    }
+                    for elem in elems.into_iter() {
+                        let target = Self::new_init_target(&var, &elem, &declarator.position);
+                        let assign = Self::new_assign(
+                            BinaryOpKind::Equal,
+                            target,
+                            elem.expr,
+                            declarator.position.clone(),
+                        )?;
+                        stmts.push(Stmt::new_expr(Some(assign), position.clone()));
+                    }
+                }
+            }
+        }
+        Ok(Stmt::new_cpd(stmts, position))
+    }
+
+    pub fn analyze_global_decl(&mut self, row_decl: RowDecl) -> anyhow::Result<()> {
+        if let Some(RowStorageClass::Typedef) = row_decl.decl_spec.storage_class {
+            return self.analyze_typedef(row_decl);
+        }
+        let storage_class = row_decl.decl_spec.storage_class.clone();
+        let base = self.resolve_decl_spec(&row_decl.decl_spec)?;
+        for init_declarator in row_decl.init_declarators.into_iter() {
+            let declarator = init_declarator.declarator;
+            let ty = self.resolve_declarator(base.clone(), &declarator)?;
+            let name = Self::declarator_name(&declarator)?;
+            if let Some(RowStorageClass::Auto) = storage_class {
+                return Err(anyhow!(
+                    "{}Invalid storage class for file-scope declaration `{}`",
+                    declarator.position,
+                    name
+                ));
+            }
+            if let Type::Function { .. } = ty {
+                self.declare_linkage(&name, &storage_class, true, &declarator.position)?;
+                self.declare_func(name, ty, &declarator.position)?;
+                continue;
+            }
+            // An `extern` declaration without an initializer only refers to a definition.
+            let is_extern = matches!(storage_class, Some(RowStorageClass::Extern))
+                && init_declarator.init.is_none();
+            let is_sized_later = is_extern || init_declarator.init.is_some();
+            Self::check_var_type(&name, &ty, is_sized_later, &declarator.position)?;
+            let linkage =
+                self.declare_linkage(&name, &storage_class, false, &declarator.position)?;
+            let is_const = Self::is_const_object(&row_decl.decl_spec, &declarator);
+            let (ty, init) = match init_declarator.init {
+                Some(row_init) => {
+                    let (ty, init) = self.analyze_global_init(ty, row_init)?;
+                    (ty, Some(init))
+                }
+                None => (ty, None),
+            };
+            let is_static = linkage == Linkage::Internal;
+            let global =
+                GlobalVariable::new(name.clone(), ty, init, is_const, is_static, is_extern);
+            self.declare_global_var(name, global, &declarator.position)?;
+        }
+        Ok(())
+    }
+
+    /// Checks the declared type of a variable. An array of unknown length is accepted if it is
+    /// `extern` or gets its length from an initializer.
+    fn check_var_type(
+        name: &str,
+        ty: &Type,
+        is_sized_later: bool,
+        position: &Position,
+    ) -> anyhow::Result<()> {
+        if *ty == Type::Void {
+            return Err(anyhow!("{}Variable `{}` declared void", position, name));
+        }
+        if ty.size() == 0 && !(is_sized_later && ty.is_array()) {
+            return Err(anyhow!(
+                "{}Variable `{}` has incomplete type",
+                position,
+                name
+            ));
+        }
+        Ok(())
+    }
+
+    fn analyze_global_init(
+        &mut self,
+        ty: Type,
+        row_init: RowInitializer,
+    ) -> anyhow::Result<(Type, GlobalInit)> {
+        let (ty, elems) = self.analyze_initializer(ty, row_init)?;
+        let mut init = GlobalInit::new(ty.size());
+        for elem in elems.iter() {
+            Self::write_global_init(&mut init, elem.offset, &elem.ty, &elem.expr)?;
+        }
+        Ok((ty, init))
+    }
+
+    /// Writes the constant value of a scalar initializer at `offset`.
+    fn write_global_init(
+        init: &mut GlobalInit,
+        offset: usize,
+        ty: &Type,
+        expr: &Expr,
+    ) -> anyhow::Result<()> {
+        if !ty.is_scalar() || !expr.ty.is_scalar() {
+            return Err(anyhow!(
+                "{}Initializer element is not a compile-time constant",
+                expr.position
+            ));
+        }
+        let size = ty.size();
+        if ty.is_float() || expr.ty.is_float() {
+            let value = Self::eval_float_const(expr).map_err(|_| {
+                anyhow!(
+                    "{}Initializer element is not a compile-time constant",
+                    expr.position
+                )
+            })?;
+            let bytes = match ty {
+                Type::Float { kind } => value.to_bits(*kind),
+                _ => Self::convert_const(value.to_integer(ty.is_signed()), ty) as u64 as u128,
+            };
+            init.relocations.retain(|relocation| {
+                relocation.offset + 8 <= offset || relocation.offset >= offset + size
+            });
+            init.bytes[offset..offset + size].copy_from_slice(&bytes.to_le_bytes()[..size]);
+            return Ok(());
+        }
+        let (label, value) = Self::eval_reloc_const(expr).map_err(|_| {
+            anyhow!(
+                "{}Initializer element is not a compile-time constant",
+                expr.position
+            )
+        })?;
+        // A later designator overrides what an earlier one stored here.
+        init.relocations.retain(|relocation| {
+            relocation.offset + 8 <= offset || relocation.offset >= offset + size
+        });
+        if let Some(label) = label {
+            if ty.size() != 8 {
+                return Err(anyhow!(
+                    "{}Initializer element is not a compile-time constant",
+                    expr.position
+                ));
+            }
+            init.relocations.push(Relocation::new(offset, label, value));
+            return Ok(());
+        }
+        let value = Self::convert_const(value, ty);
+        init.bytes[offset..offset + size].copy_from_slice(&value.to_le_bytes()[..size]);
+        Ok(())
+    }
+
+    /// Whether the object itself, not only what it points to, is `const`.
+    fn is_const_object(decl_spec: &RowDeclSpec, declarator: &RowDeclarator) -> bool {
+        declarator
+            .derived
+            .iter()
+            .fold(decl_spec.is_const, |is_const, derived| match derived {
+                RowDerived::Pointer { is_const } => *is_const,
+                RowDerived::Array { .. } => is_const,
+                RowDerived::Function { .. } => false,
+            })
+    }
+
+    fn analyze_typedef(&mut self, row_decl: RowDecl) -> anyhow::Result<()> {
+        let base = self.resolve_decl_spec(&row_decl.decl_spec)?;
+        for init_declarator in row_decl.init_declarators.into_iter() {
+            let declarator = init_declarator.declarator;
+            if init_declarator.init.is_some() {
+                return Err(anyhow!(
+                    "{}Illegal initializer in typedef",
+                    declarator.position
+                ));
+            }
+            let ty = self.resolve_declarator(base.clone(), &declarator)?;
+            let name = Self::declarator_name(&declarator)?;
+            // A typedef may be repeated with the same type.
+            if let Some(Symbol::Typedef { ty: prev_ty }) =
+                self.scopes.last().unwrap().symbols.get(&name)
+            {
+                if *prev_ty == ty {
+                    continue;
+                }
+            }
+            self.declare_symbol(name, Symbol::Typedef { ty }, &declarator.position)?;
+        }
+        Ok(())
+    }
+
+    pub fn resolve_type(
+        &mut self,
+        decl_spec: &RowDeclSpec,
+        declarator: &RowDeclarator,
+    ) -> anyhow::Result<Type> {
+        let base = self.resolve_decl_spec(decl_spec)?;
+        self.resolve_declarator(base, declarator)
+    }
+
+    fn resolve_declarator(
+        &mut self,
+        base: Type,
+        declarator: &RowDeclarator,
+    ) -> anyhow::Result<Type> {
+        let mut ty = base;
+        for derived in declarator.derived.iter() {
+            ty = match derived {
+                RowDerived::Pointer { .. } => Type::new_pointer(ty),
+                RowDerived::Array { len } => {
+                    let len = if let Some(len) = len {
+                        let expr = self.analyze_expr(len.clone())?;
+                        let len = Self::eval_const_expr(&expr)?;
+                        if expr.ty.is_signed() && len < 0 {
+                            return Err(anyhow!("{}Array size is negative", expr.position));
+                        }
+                        // Sizes are kept below `isize::MAX` so that pointer differences hold them.
+                        match ty.size().checked_mul(len as usize) {
+                            Some(size) if size <= isize::MAX as usize => {}
+                            _ => return Err(anyhow!("{}Array is too large", expr.position)),
+                        }
+                        len as usize
+                    } else {
+                        0
+                    };
+                    Type::new_array(ty, len)
+                }
+                RowDerived::Function { params, variadic } => {
+                    let params = params
+                        .iter()
+                        .map(|param| self.resolve_param_type(&param.decl_spec, &param.declarator))
+                        .collect::<anyhow::Result<Vec<Type>>>()?;
+                    if ty.is_struct() || params.iter().any(Type::is_struct) {
+                        return Err(anyhow!(
+                            "{}Passing struct by value is not supported",
+                            declarator.position
+                        ));
+                    }
+                    Type::new_function(ty, params, *variadic)
+                }
+            };
+        }
+        Ok(ty)
+    }
+
+    /// Resolves a parameter type, adjusting arrays to pointers.
+    pub fn resolve_param_type(
+        &mut self,
+        decl_spec: &RowDeclSpec,
+        declarator: &RowDeclarator,
+    ) -> anyhow::Result<Type> {
+        Ok(self.resolve_type(decl_spec, declarator)?.decay())
+    }
+
+    fn resolve_decl_spec(&mut self, decl_spec: &RowDeclSpec) -> anyhow::Result<Type> {
+        Ok(match &decl_spec.type_spec {
+            RowTypeSpec::Void => Type::Void,
+            RowTypeSpec::Char | RowTypeSpec::SignedChar => {
+                Type::new_integer(IntegerKind::Char, true)
+            }
+            RowTypeSpec::UnsignedChar => Type::new_integer(IntegerKind::Char, false),
+            RowTypeSpec::Short => Type::new_integer(IntegerKind::Short, true),
+            RowTypeSpec::UnsignedShort => Type::new_integer(IntegerKind::Short, false),
+            RowTypeSpec::Int => Type::new_integer(IntegerKind::Int, true),
+            RowTypeSpec::UnsignedInt => Type::new_integer(IntegerKind::Int, false),
+            RowTypeSpec::Long => Type::new_integer(IntegerKind::Long, true),
+            RowTypeSpec::UnsignedLong => Type::new_integer(IntegerKind::Long, false),
+            RowTypeSpec::LongLong => Type::new_integer(IntegerKind::LongLong, true),
+            RowTypeSpec::UnsignedLongLong => Type::new_integer(IntegerKind::LongLong, false),
+            RowTypeSpec::Float => Type::new_float(FloatKind::Float),
+            RowTypeSpec::Double => Type::new_float(FloatKind::Double),
+            RowTypeSpec::LongDouble => Type::new_float(FloatKind::LongDouble),
+            RowTypeSpec::Struct {
+                is_union,
+                tag,
+                members,
+                position,
+            } => self.resolve_struct_spec(*is_union, tag, members, position)?,
+            RowTypeSpec::TypedefName { name, position } => match self.get_symbol(name, position)? {
+                Symbol::Typedef { ty } => ty,
+                _ => return Err(anyhow!("{}`{}` is not a type name", position, name)),
+            },
+            RowTypeSpec::Enum {
+                tag,
+                enumerators,
+                position,
+            } => self.resolve_enum_spec(tag, enumerators, position)?,
+        })
+    }
+
+    fn resolve_struct_spec(
+        &mut self,
+        is_union: bool,
+        tag: &Option<String>,
+        row_members: &Option<Vec<RowMember>>,
+        position: &Position,
+    ) -> anyhow::Result<Type> {
+        let struct_type = match tag {
+            Some(tag) => match self.get_tag(tag, row_members.is_some()) {
+                Some(Tag::Struct { struct_type }) if struct_type.is_union == is_union => {
+                    if row_members.is_some() && struct_type.is_complete() {
+                        return Err(anyhow!("{}Redefinition of `{}`", position, tag));
+                    }
+                    struct_type
+                }
+                Some(_) => {
+                    return Err(anyhow!(
+                        "{}Use of `{}` with tag type that does not match previous declaration",
+                        position,
+                        tag
+                    ));
+                }
+                None => {
+                    let struct_type = Rc::new(StructType::new(Some(tag.clone()), is_union));
+                    let tag_entry = Tag::Struct {
+                        struct_type: struct_type.clone(),
+                    };
+                    self.declare_tag(tag.clone(), tag_entry);
+                    struct_type
+                }
+            },
+            None => Rc::new(StructType::new(None, is_union)),
+        };
+        if let Some(row_members) = row_members {
+            let mut members: Vec<(Option<String>, Type)> = Vec::new();
+            for row_member in row_members.iter() {
+                let base = self.resolve_decl_spec(&row_member.decl_spec)?;
+                if row_member.declarators.is_empty() {
+                    if let Type::Struct { struct_type } = &base {
+                        if struct_type.tag.is_none() {
+                            members.push((None, base));
+                        }
+                    }
+                    continue;
+                }
+                for declarator in row_member.declarators.iter() {
+                    let ty = self.resolve_declarator(base.clone(), declarator)?;
+                    let name = Self::declarator_name(declarator)?;
+                    if ty.size() == 0 && !ty.is_array() {
+                        return Err(anyhow!(
+                            "{}Member `{}` has incomplete type",
+                            declarator.position,
+                            name
+                        ));
+                    }
+                    if members
+                        .iter()
+                        .any(|(member, _)| member.as_ref() == Some(&name))
+                    {
+                        return Err(anyhow!(
+                            "{}Duplicate member `{}`",
+                            declarator.position,
+                            name
+                        ));
+                    }
+                    members.push((Some(name), ty));
+                }
+            }
+            struct_type.define(members);
+        }
+        Ok(Type::Struct { struct_type })
+    }
+
+    fn resolve_enum_spec(
+        &mut self,
+        tag: &Option<String>,
+        enumerators: &Option<Vec<RowEnumerator>>,
+        position: &Position,
+    ) -> anyhow::Result<Type> {
+        if let Some(tag) = tag {
+            match self.get_tag(tag, enumerators.is_some()) {
+                Some(Tag::Enum) => {
+                    if enumerators.is_some() {
+                        return Err(anyhow!("{}Redefinition of `{}`", position, tag));
+                    }
+                }
+                Some(_) => {
+                    return Err(anyhow!(
+                        "{}Use of `{}` with tag type that does not match previous declaration",
+                        position,
+                        tag
+                    ));
+                }
+                None => {
+                    if enumerators.is_none() {
+                        return Err(anyhow!("{}Use of undeclared enum `{}`", position, tag));
+                    }
+                    self.declare_tag(tag.clone(), Tag::Enum);
+                }
+            }
+        }
+        let mut value = 0;
+        for enumerator in enumerators.iter().flatten() {
+            if let Some(row_value) = &enumerator.value {
+                let expr = self.analyze_expr(row_value.clone())?;
+                value = Self::eval_const_expr(&expr)?;
+            }
+            if i32::try_from(value).is_err() {
+                return Err(anyhow!(
+                    "{}Enumerator value is not representable in int",
+                    enumerator.position
+                ));
+            }
+            let symbol = Symbol::EnumConst { value };
+            self.declare_symbol(enumerator.name.clone(), symbol, &enumerator.position)?;
+            value += 1;
+        }
+        Ok(Type::new_int())
+    }
+
+    pub fn declarator_name(declarator: &RowDeclarator) -> anyhow::Result<String> {
+        declarator
+            .name
+            .clone()
+            .ok_or_else(|| anyhow!("{}Expect identifier", declarator.position))
+    }
+}
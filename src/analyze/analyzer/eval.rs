@@ -0,0 +1,226 @@
+use std::cmp::Ordering;
+
+use anyhow::anyhow;
+
+use crate::analyze::{
+    expr::{BinaryOpKind, Expr, ExprKind, UnaryOpKind},
+    float_value::FloatValue,
+    types::Type,
+    variable::{Variable, VariableKind},
+};
+
+use super::Analyzer;
+
+impl Analyzer {
+    /// Evaluates an integer constant expression.
+    pub fn eval_const_expr(expr: &Expr) -> anyhow::Result<i64> {
+        Ok(match &expr.kind {
+            ExprKind::Number { number } => Self::convert_const(*number as i64, &expr.ty),
+            ExprKind::Unary {
+                op_kind,
+                expr: inner,
+            } => {
+                let value = Self::eval_const_expr(inner)?;
+                let value = match op_kind {
+                    UnaryOpKind::Neg => value.wrapping_neg(),
+                    UnaryOpKind::BitNot => !value,
+                    UnaryOpKind::LogicNot => (value == 0) as i64,
+                };
+                Self::convert_const(value, &expr.ty)
+            }
+            ExprKind::Binary { op_kind, lhs, rhs } if lhs.ty.is_float() => {
+                let lhs = Self::eval_float_const(lhs)?;
+                let rhs = Self::eval_float_const(rhs)?;
+                let ordering = lhs.partial_cmp(rhs);
+                match op_kind {
+                    BinaryOpKind::Lt => (ordering == Some(Ordering::Less)) as i64,
+                    BinaryOpKind::LtEqual => {
+                        matches!(ordering, Some(Ordering::Less | Ordering::Equal)) as i64
+                    }
+                    BinaryOpKind::Equal => (ordering == Some(Ordering::Equal)) as i64,
+                    BinaryOpKind::NotEqual => (ordering != Some(Ordering::Equal)) as i64,
+                    _ => unreachable!(),
+                }
+            }
+            ExprKind::Binary { op_kind, lhs, rhs } => {
+                // Shifts take their signedness from the promoted left operand, which is the type
+                // of the result.
+                let is_signed = match op_kind {
+                    BinaryOpKind::LShift | BinaryOpKind::RShift => expr.ty.is_signed(),
+                    _ => lhs.ty.is_signed(),
+                };
+                let lhs = Self::eval_const_expr(lhs)?;
+                let rhs = Self::eval_const_expr(rhs)?;
+                let value = match op_kind {
+                    BinaryOpKind::Add => lhs.wrapping_add(rhs),
+                    BinaryOpKind::Sub => lhs.wrapping_sub(rhs),
+                    BinaryOpKind::Mul => lhs.wrapping_mul(rhs),
+                    BinaryOpKind::Div | BinaryOpKind::Rem if rhs == 0 => {
+                        return Err(anyhow!(
+                            "{}Division by zero in constant expression",
+                            expr.position
+                        ));
+                    }
+                    BinaryOpKind::Div if is_signed => lhs.wrapping_div(rhs),
+                    BinaryOpKind::Div => (lhs as u64 / rhs as u64) as i64,
+                    BinaryOpKind::Rem if is_signed => lhs.wrapping_rem(rhs),
+                    BinaryOpKind::Rem => (lhs as u64 % rhs as u64) as i64,
+                    BinaryOpKind::BitAnd => lhs & rhs,
+                    BinaryOpKind::BitOr => lhs | rhs,
+                    BinaryOpKind::BitXor => lhs ^ rhs,
+                    BinaryOpKind::LShift => lhs.wrapping_shl(rhs as u32),
+                    BinaryOpKind::RShift if is_signed => lhs.wrapping_shr(rhs as u32),
+                    BinaryOpKind::RShift => (lhs as u64).wrapping_shr(rhs as u32) as i64,
+                    BinaryOpKind::Lt if is_signed => (lhs < rhs) as i64,
+                    BinaryOpKind::Lt => ((lhs as u64) < rhs as u64) as i64,
+                    BinaryOpKind::LtEqual if is_signed => (lhs <= rhs) as i64,
+                    BinaryOpKind::LtEqual => (lhs as u64 <= rhs as u64) as i64,
+                    BinaryOpKind::Equal => (lhs == rhs) as i64,
+                    BinaryOpKind::NotEqual => (lhs != rhs) as i64,
+                };
+                Self::convert_const(value, &expr.ty)
+            }
+            ExprKind::Cast { expr: inner } if inner.ty.is_float() => {
+                let value = Self::eval_float_const(inner)?.to_integer(expr.ty.is_signed());
+                Self::convert_const(value, &expr.ty)
+            }
+            ExprKind::Cast { expr: inner } => {
+                Self::convert_const(Self::eval_const_expr(inner)?, &expr.ty)
+            }
+            ExprKind::Condition {
+                condition,
+                then_expr,
+                else_expr,
+            } => {
+                if Self::eval_const_expr(condition)? != 0 {
+                    Self::eval_const_expr(then_expr)?
+                } else {
+                    Self::eval_const_expr(else_expr)?
+                }
+            }
+            _ => {
+                return Err(anyhow!(
+                    "{}Expression is not an integer constant expression",
+                    expr.position
+                ))
+            }
+        })
+    }
+
+    /// Evaluates an arithmetic constant expression of floating type, or an integer one converted
+    /// to floating.
+    pub fn eval_float_const(expr: &Expr) -> anyhow::Result<FloatValue> {
+        if expr.ty.is_integer() {
+            let value = Self::eval_const_expr(expr)?;
+            return Ok(FloatValue::from_integer(value, expr.ty.is_signed()));
+        }
+        let value = match &expr.kind {
+            ExprKind::FloatNumber { value } => *value,
+            ExprKind::Cast { expr: inner } => Self::eval_float_const(inner)?,
+            ExprKind::Unary {
+                op_kind: UnaryOpKind::Neg,
+                expr,
+            } => Self::eval_float_const(expr)?.neg(),
+            ExprKind::Binary { op_kind, lhs, rhs } => {
+                let Type::Float { kind } = expr.ty else {
+                    unreachable!()
+                };
+                let lhs = Self::eval_float_const(lhs)?;
+                let rhs = Self::eval_float_const(rhs)?;
+                match op_kind {
+                    BinaryOpKind::Add => lhs.add(rhs, kind),
+                    BinaryOpKind::Sub => lhs.sub(rhs, kind),
+                    BinaryOpKind::Mul => lhs.mul(rhs, kind),
+                    BinaryOpKind::Div => lhs.div(rhs, kind),
+                    _ => unreachable!(),
+                }
+            }
+            ExprKind::Condition {
+                condition,
+                then_expr,
+                else_expr,
+            } => {
+                if Self::eval_const_expr(condition)? != 0 {
+                    Self::eval_float_const(then_expr)?
+                } else {
+                    Self::eval_float_const(else_expr)?
+                }
+            }
+            _ => {
+                return Err(anyhow!(
+                    "{}Expression is not an arithmetic constant expression",
+                    expr.position
+                ))
+            }
+        };
+        Ok(Self::round_float(value, &expr.ty))
+    }
+
+    /// Rounds a value to the precision of the floating type `ty`.
+    pub fn round_float(value: FloatValue, ty: &Type) -> FloatValue {
+        match ty {
+            Type::Float { kind } => value.round(*kind),
+            _ => value,
+        }
+    }
+
+    /// Converts a constant to `ty`, truncating and extending it as the generator would.
+    pub fn convert_const(value: i64, ty: &Type) -> i64 {
+        match (ty.size(), ty.is_signed()) {
+            (1, true) => value as i8 as i64,
+            (1, false) => value as u8 as i64,
+            (2, true) => value as i16 as i64,
+            (2, false) => value as u16 as i64,
+            (4, true) => value as i32 as i64,
+            (4, false) => value as u32 as i64,
+            _ => value,
+        }
+    }
+
+    /// Evaluates a constant that may contain the address of a global, as a label and an addend.
+    pub fn eval_reloc_const(expr: &Expr) -> anyhow::Result<(Option<String>, i64)> {
+        match &expr.kind {
+            ExprKind::Addr { expr } => return Self::eval_const_address(expr),
+            ExprKind::Cast { expr: inner } if expr.ty.size() == 8 && !inner.ty.is_float() => {
+                return Self::eval_reloc_const(inner);
+            }
+            ExprKind::Binary {
+                op_kind: op_kind @ (BinaryOpKind::Add | BinaryOpKind::Sub),
+                lhs,
+                rhs,
+            } if expr.ty.is_pointer() => {
+                let (label, lhs) = Self::eval_reloc_const(lhs)?;
+                let rhs = Self::eval_const_expr(rhs)?;
+                let value = if let BinaryOpKind::Add = op_kind {
+                    lhs.wrapping_add(rhs)
+                } else {
+                    lhs.wrapping_sub(rhs)
+                };
+                return Ok((label, value));
+            }
+            _ => {}
+        }
+        Ok((None, Self::eval_const_expr(expr)?))
+    }
+
+    fn eval_const_address(expr: &Expr) -> anyhow::Result<(Option<String>, i64)> {
+        match &expr.kind {
+            ExprKind::Variable {
+                var:
+                    Variable {
+                        kind: VariableKind::Global { name },
+                        ..
+                    },
+            } => Ok((Some(name.clone()), 0)),
+            ExprKind::Member { expr, offset } => {
+                let (label, value) = Self::eval_const_address(expr)?;
+                Ok((label, value.wrapping_add(*offset as i64)))
+            }
+            ExprKind::Deref { expr } => Self::eval_reloc_const(expr),
+            _ => Err(anyhow!(
+                "{}Initializer element is not a compile-time constant",
+                expr.position
+            )),
+        }
+    }
+}
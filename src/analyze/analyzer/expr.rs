@@ -1,13 +1,40 @@
+use anyhow::anyhow;
+
 use crate::{
-    analyze::expr::{BinaryOpKind, Expr},
+    analyze::{
+        expr::{BinaryOpKind, Expr, ExprKind, UnaryOpKind},
+        float_value::FloatValue,
+        global_variable::{GlobalInit, GlobalVariable},
+        types::{FloatKind, IntegerKind, Type},
+        variable::Variable,
+    },
     file::position::Position,
-    parse::row_expr::{RowAssignOpKind, RowBinaryOpKind, RowExpr, RowExprKind},
+    parse::row_expr::{RowAssignOpKind, RowBinaryOpKind, RowExpr, RowExprKind, RowUnaryOpKind},
+    tokenize::token_kind::{Encoding, FloatSuffix, IntegerFormat},
 };
 
-use super::Analyzer;
+use super::{Analyzer, Symbol};
 
 impl Analyzer {
     pub fn analyze_expr(&mut self, row_expr: RowExpr) -> anyhow::Result<Expr> {
+        let expr = self.analyze_expr_or_void(row_expr)?;
+        if expr.ty == Type::Void {
+            return Err(anyhow!(
+                "{}Void value not ignored as it ought to be",
+                expr.position
+            ));
+        }
+        Ok(expr)
+    }
+
+    /// Analyzes an expression whose value is discarded, which may be `void`.
+    pub fn analyze_expr_or_void(&mut self, row_expr: RowExpr) -> anyhow::Result<Expr> {
+        let expr = self.analyze_expr_without_decay(row_expr)?;
+        Ok(Self::decay(expr))
+    }
+
+    /// Analyzes an operand of `&` or `sizeof`, which keeps its array type.
+    fn analyze_expr_without_decay(&mut self, row_expr: RowExpr) -> anyhow::Result<Expr> {
         let position = row_expr.position;
         Ok(match row_expr.kind {
             RowExprKind::Binary {
@@ -34,56 +61,205 @@ impl Analyzer {
                 row_lhs_expr,
                 row_rhs_expr,
             } => Expr::new_comma(
-                self.analyze_expr(*row_lhs_expr)?,
-                self.analyze_expr(*row_rhs_expr)?,
+                self.analyze_expr_or_void(*row_lhs_expr)?,
+                self.analyze_expr_or_void(*row_rhs_expr)?,
                 position,
             ),
             RowExprKind::Condition {
                 row_condition_expr,
                 row_then_expr,
                 row_else_expr,
-            } => Expr::new_condition(
-                self.analyze_expr(*row_condition_expr)?,
-                self.analyze_expr(*row_then_expr)?,
-                self.analyze_expr(*row_else_expr)?,
-                position,
-            ),
-            RowExprKind::UnaryIncrement { row_expr } => Expr::new_assign(
+            } => {
+                let condition = Self::new_condition_value(self.analyze_expr(*row_condition_expr)?)?;
+                let then_expr = self.analyze_expr_or_void(*row_then_expr)?;
+                let else_expr = self.analyze_expr_or_void(*row_else_expr)?;
+                let (then_expr, else_expr, ty) =
+                    Self::convert_condition_operands(then_expr, else_expr, &position)?;
+                Expr::new_condition(condition, then_expr, else_expr, ty, position)
+            }
+            RowExprKind::Unary {
+                row_unary_op_kind,
+                row_expr,
+            } => self.analyze_expr_unary(row_unary_op_kind, *row_expr, position)?,
+            RowExprKind::Cast {
+                row_type_name,
+                row_expr,
+            } => {
+                let ty = self.resolve_type(&row_type_name.decl_spec, &row_type_name.declarator)?;
+                let expr = if ty == Type::Void {
+                    self.analyze_expr_or_void(*row_expr)?
+                } else {
+                    self.analyze_expr(*row_expr)?
+                };
+                if ty != Type::Void && !(ty.is_scalar() && expr.ty.is_scalar())
+                    || (ty.is_float() && expr.ty.is_pointer())
+                    || (ty.is_pointer() && expr.ty.is_float())
+                {
+                    return Err(anyhow!("{}Invalid cast", position));
+                }
+                Expr::new_cast(expr, ty, position)
+            }
+            RowExprKind::SizeofExpr { row_expr } => {
+                let ty = self.analyze_expr_without_decay(*row_expr)?.ty;
+                Self::new_sizeof(&ty, position)?
+            }
+            RowExprKind::SizeofType { row_type_name } => {
+                let ty = self.resolve_type(&row_type_name.decl_spec, &row_type_name.declarator)?;
+                Self::new_sizeof(&ty, position)?
+            }
+            RowExprKind::AlignofType { row_type_name } => {
+                let ty = self.resolve_type(&row_type_name.decl_spec, &row_type_name.declarator)?;
+                if ty.size() == 0 {
+                    return Err(anyhow!(
+                        "{}Invalid application of `_Alignof` to an incomplete type",
+                        position
+                    ));
+                }
+                Expr::new_number(ty.align(), Type::new_unsigned_long(), position)
+            }
+            RowExprKind::UnaryIncrement { row_expr } => Self::new_assign(
                 BinaryOpKind::Add,
                 self.analyze_expr(*row_expr)?,
-                Expr::new_number(1, position.clone()),
+                Expr::new_number(1, Type::new_int(), position.clone()),
                 position,
-            ),
-            RowExprKind::UnaryDecrement { row_expr } => Expr::new_assign(
+            )?,
+            RowExprKind::UnaryDecrement { row_expr } => Self::new_assign(
                 BinaryOpKind::Sub,
                 self.analyze_expr(*row_expr)?,
-                Expr::new_number(1, position.clone()),
+                Expr::new_number(1, Type::new_int(), position.clone()),
                 position,
-            ),
+            )?,
             RowExprKind::PostfixIncrement { row_expr } => {
                 Expr::new_postfix_increment(self.analyze_expr(*row_expr)?, position)
             }
             RowExprKind::PostfixDecrement { row_expr } => {
                 Expr::new_postfix_decrement(self.analyze_expr(*row_expr)?, position)
             }
-            RowExprKind::Identifier { ident } => {
-                let var = self.get_var(ident);
+            RowExprKind::Identifier { ident } => match self.get_symbol(&ident, &position)? {
+                Symbol::Variable { var } => Expr::new_var(var, position),
+                Symbol::EnumConst { value } => {
+                    Expr::new_number(value as usize, Type::new_int(), position)
+                }
+                Symbol::Typedef { .. } => {
+                    return Err(anyhow!("{}Unexpected type name `{}`", position, ident));
+                }
+            },
+            RowExprKind::Number { number, format } => {
+                let ty = Self::integer_constant_type(number, format, &position);
+                Expr::new_number(number, ty, position)
+            }
+            RowExprKind::FloatNumber { literal, suffix } => {
+                let kind = match suffix {
+                    FloatSuffix::Float => FloatKind::Float,
+                    FloatSuffix::Double => FloatKind::Double,
+                    FloatSuffix::LongDouble => FloatKind::LongDouble,
+                };
+                let value = FloatValue::parse(&literal, kind);
+                Expr::new_float_number(value, Type::new_float(kind), position)
+            }
+            RowExprKind::Character { encoding, value } => {
+                let ty = match encoding {
+                    Encoding::Char => Type::new_int(),
+                    Encoding::Utf8 => Type::new_integer(IntegerKind::Char, false),
+                    encoding => Self::string_elem_type(encoding),
+                };
+                Expr::new_number(value as usize, ty, position)
+            }
+            RowExprKind::Str { encoding, units } => {
+                let var = self.new_string_literal(encoding, units);
                 Expr::new_var(var, position)
             }
-            RowExprKind::Number { number } => Expr::new_number(number, position),
             RowExprKind::Func {
                 row_name_expr,
                 row_args_expr,
-            } => {
-                let args = row_args_expr
-                    .into_iter()
-                    .map(|arg| self.analyze_expr(arg))
-                    .collect::<anyhow::Result<Vec<Expr>>>()?;
-                if let RowExprKind::Identifier { ident } = row_name_expr.kind {
-                    Expr::new_func_label(ident, args, position)
-                } else {
-                    Expr::new_func_expr(self.analyze_expr(*row_name_expr)?, args, position)
+            } => self.analyze_expr_func(*row_name_expr, row_args_expr, position)?,
+            RowExprKind::Member { row_expr, member } => {
+                let expr = self.analyze_expr(*row_expr)?;
+                let Type::Struct { struct_type } = &expr.ty else {
+                    return Err(anyhow!(
+                        "{}Member reference base type is not a structure",
+                        position
+                    ));
+                };
+                let Some(member) = struct_type.find_member(&member) else {
+                    return Err(anyhow!("{}No member named `{}`", position, member));
+                };
+                Expr::new_member(expr, member.offset, member.ty, position)
+            }
+        })
+    }
+
+    fn analyze_expr_unary(
+        &mut self,
+        row_unary_op_kind: RowUnaryOpKind,
+        row_expr: RowExpr,
+        position: Position,
+    ) -> anyhow::Result<Expr> {
+        let expr = if let RowUnaryOpKind::Addr = row_unary_op_kind {
+            self.analyze_expr_without_decay(row_expr)?
+        } else {
+            self.analyze_expr(row_expr)?
+        };
+        Ok(match row_unary_op_kind {
+            RowUnaryOpKind::Addr => {
+                if !matches!(
+                    expr.kind,
+                    ExprKind::Variable { .. } | ExprKind::Deref { .. } | ExprKind::Member { .. }
+                ) {
+                    return Err(anyhow!("{}Cannot take the address of an rvalue", position));
+                }
+                let ty = Type::new_pointer(expr.ty.clone());
+                Expr::new_addr(expr, ty, position)
+            }
+            RowUnaryOpKind::Deref => {
+                let Some(ty) = expr.ty.pointee().cloned() else {
+                    return Err(anyhow!("{}Indirection requires pointer operand", position));
+                };
+                Expr::new_deref(expr, ty, position)
+            }
+            RowUnaryOpKind::Plus => {
+                if !expr.ty.is_arithmetic() {
+                    return Err(anyhow!(
+                        "{}Invalid argument type to unary expression",
+                        position
+                    ));
                 }
+                // Adding zero would turn -0.0 into 0.0.
+                if expr.ty.is_float() {
+                    let ty = expr.ty.clone();
+                    return Ok(Expr::new_cast(expr, ty, position));
+                }
+                let ty = expr.ty.promote();
+                Expr::new_binary(
+                    BinaryOpKind::Add,
+                    Expr::new_number(0, Type::new_int(), position.clone()),
+                    expr,
+                    ty,
+                    position,
+                )
+            }
+            RowUnaryOpKind::Minus | RowUnaryOpKind::BitNot => {
+                let is_valid = match row_unary_op_kind {
+                    RowUnaryOpKind::Minus => expr.ty.is_arithmetic(),
+                    _ => expr.ty.is_integer(),
+                };
+                if !is_valid {
+                    return Err(anyhow!(
+                        "{}Invalid argument type to unary expression",
+                        position
+                    ));
+                }
+                let op_kind = if let RowUnaryOpKind::Minus = row_unary_op_kind {
+                    UnaryOpKind::Neg
+                } else {
+                    UnaryOpKind::BitNot
+                };
+                let ty = expr.ty.promote();
+                Expr::new_unary(op_kind, expr, ty, position)
+            }
+            RowUnaryOpKind::LogicNot => {
+                let expr = Self::new_condition_value(expr)?;
+                Expr::new_unary(UnaryOpKind::LogicNot, expr, Type::new_int(), position)
             }
         })
     }
@@ -95,40 +271,262 @@ impl Analyzer {
         row_rhs_expr: RowExpr,
         position: Position,
     ) -> anyhow::Result<Expr> {
+        let lhs = self.analyze_expr(row_lhs_expr)?;
+        let rhs = self.analyze_expr(row_rhs_expr)?;
         Ok(match row_binary_op_kind {
-            RowBinaryOpKind::LogicAnd => Expr::new_condition(
-                self.analyze_expr(row_lhs_expr)?,
-                self.analyze_expr(row_rhs_expr)?,
-                Expr::new_number(0, position.clone()),
-                position,
-            ),
-            RowBinaryOpKind::LogicOr => Expr::new_condition(
-                self.analyze_expr(row_lhs_expr)?,
-                Expr::new_number(1, position.clone()),
-                self.analyze_expr(row_rhs_expr)?,
-                position,
-            ),
-            RowBinaryOpKind::Gt => Expr::new_binary(
-                BinaryOpKind::Lt,
-                self.analyze_expr(row_rhs_expr)?,
-                self.analyze_expr(row_lhs_expr)?,
-                position,
-            ),
-            RowBinaryOpKind::GtEqual => Expr::new_binary(
-                BinaryOpKind::LtEqual,
-                self.analyze_expr(row_rhs_expr)?,
-                self.analyze_expr(row_lhs_expr)?,
-                position,
-            ),
-            op_kind => Expr::new_binary(
-                BinaryOpKind::from_row_binary_op_kind(op_kind)?,
-                self.analyze_expr(row_lhs_expr)?,
-                self.analyze_expr(row_rhs_expr)?,
-                position,
-            ),
+            RowBinaryOpKind::LogicAnd => {
+                Self::check_scalar_operands(&lhs, &rhs, &position)?;
+                let lhs = Self::new_condition_value(lhs)?;
+                let rhs = Self::new_truth_value(rhs);
+                Expr::new_condition(
+                    lhs,
+                    rhs,
+                    Expr::new_number(0, Type::new_int(), position.clone()),
+                    Type::new_int(),
+                    position,
+                )
+            }
+            RowBinaryOpKind::LogicOr => {
+                Self::check_scalar_operands(&lhs, &rhs, &position)?;
+                let lhs = Self::new_condition_value(lhs)?;
+                let rhs = Self::new_truth_value(rhs);
+                Expr::new_condition(
+                    lhs,
+                    Expr::new_number(1, Type::new_int(), position.clone()),
+                    rhs,
+                    Type::new_int(),
+                    position,
+                )
+            }
+            RowBinaryOpKind::Add => Self::new_add(lhs, rhs, position)?,
+            RowBinaryOpKind::Sub => Self::new_sub(lhs, rhs, position)?,
+            RowBinaryOpKind::Gt => {
+                let (lhs, rhs) = Self::convert_comparison_operands(lhs, rhs, &position)?;
+                Expr::new_binary(BinaryOpKind::Lt, rhs, lhs, Type::new_int(), position)
+            }
+            RowBinaryOpKind::GtEqual => {
+                let (lhs, rhs) = Self::convert_comparison_operands(lhs, rhs, &position)?;
+                Expr::new_binary(BinaryOpKind::LtEqual, rhs, lhs, Type::new_int(), position)
+            }
+            RowBinaryOpKind::Lt
+            | RowBinaryOpKind::LtEqual
+            | RowBinaryOpKind::Equal
+            | RowBinaryOpKind::NotEqual => {
+                let (lhs, rhs) = Self::convert_comparison_operands(lhs, rhs, &position)?;
+                Expr::new_binary(
+                    BinaryOpKind::from_row_binary_op_kind(row_binary_op_kind)?,
+                    lhs,
+                    rhs,
+                    Type::new_int(),
+                    position,
+                )
+            }
+            RowBinaryOpKind::LShift | RowBinaryOpKind::RShift => {
+                Self::check_integer_operands(&lhs, &rhs, &position)?;
+                let ty = lhs.ty.promote();
+                Expr::new_binary(
+                    BinaryOpKind::from_row_binary_op_kind(row_binary_op_kind)?,
+                    lhs,
+                    rhs,
+                    ty,
+                    position,
+                )
+            }
+            op_kind => {
+                if let RowBinaryOpKind::Mul | RowBinaryOpKind::Div = op_kind {
+                    Self::check_arithmetic_operands(&lhs, &rhs, &position)?;
+                } else {
+                    Self::check_integer_operands(&lhs, &rhs, &position)?;
+                }
+                let ty = Type::common(&lhs.ty, &rhs.ty);
+                let lhs = Self::new_convert(lhs, &ty);
+                let rhs = Self::new_convert(rhs, &ty);
+                Expr::new_binary(
+                    BinaryOpKind::from_row_binary_op_kind(op_kind)?,
+                    lhs,
+                    rhs,
+                    ty,
+                    position,
+                )
+            }
         })
     }
 
+    fn new_add(lhs: Expr, rhs: Expr, position: Position) -> anyhow::Result<Expr> {
+        if lhs.ty.is_arithmetic() && rhs.ty.is_arithmetic() {
+            let ty = Type::common(&lhs.ty, &rhs.ty);
+            let lhs = Self::new_convert(lhs, &ty);
+            let rhs = Self::new_convert(rhs, &ty);
+            return Ok(Expr::new_binary(BinaryOpKind::Add, lhs, rhs, ty, position));
+        }
+        let (lhs, rhs) = if rhs.ty.is_pointer() {
+            (rhs, lhs)
+        } else {
+            (lhs, rhs)
+        };
+        let Some(size) = lhs.ty.pointee().map(|ty| ty.size()) else {
+            return Err(anyhow!("{}Invalid operands to binary expression", position));
+        };
+        if !rhs.ty.is_integer() {
+            return Err(anyhow!("{}Invalid operands to binary expression", position));
+        }
+        let ty = lhs.ty.clone();
+        let rhs = Self::new_scaled(rhs, size);
+        Ok(Expr::new_binary(BinaryOpKind::Add, lhs, rhs, ty, position))
+    }
+
+    fn new_sub(lhs: Expr, rhs: Expr, position: Position) -> anyhow::Result<Expr> {
+        if lhs.ty.is_arithmetic() && rhs.ty.is_arithmetic() {
+            let ty = Type::common(&lhs.ty, &rhs.ty);
+            let lhs = Self::new_convert(lhs, &ty);
+            let rhs = Self::new_convert(rhs, &ty);
+            return Ok(Expr::new_binary(BinaryOpKind::Sub, lhs, rhs, ty, position));
+        }
+        let Some(size) = lhs.ty.pointee().map(|ty| ty.size()) else {
+            return Err(anyhow!("{}Invalid operands to binary expression", position));
+        };
+        if rhs.ty.is_integer() {
+            let ty = lhs.ty.clone();
+            let rhs = Self::new_scaled(rhs, size);
+            return Ok(Expr::new_binary(BinaryOpKind::Sub, lhs, rhs, ty, position));
+        }
+        if lhs.ty != rhs.ty {
+            return Err(anyhow!("{}Invalid operands to binary expression", position));
+        }
+        let diff = Expr::new_binary(
+            BinaryOpKind::Sub,
+            lhs,
+            rhs,
+            Type::new_long(),
+            position.clone(),
+        );
+        Ok(Expr::new_binary(
+            BinaryOpKind::Div,
+            diff,
+            Expr::new_number(size, Type::new_long(), position.clone()),
+            Type::new_long(),
+            position,
+        ))
+    }
+
+    /// Multiplies an integer offset by the size of the pointed-to type.
+    fn new_scaled(expr: Expr, size: usize) -> Expr {
+        let position = expr.position.clone();
+        Expr::new_binary(
+            BinaryOpKind::Mul,
+            expr,
+            Expr::new_number(size, Type::new_long(), position.clone()),
+            Type::new_long(),
+            position,
+        )
+    }
+
+    fn check_integer_operands(lhs: &Expr, rhs: &Expr, position: &Position) -> anyhow::Result<()> {
+        if !lhs.ty.is_integer() || !rhs.ty.is_integer() {
+            return Err(anyhow!("{}Invalid operands to binary expression", position));
+        }
+        Ok(())
+    }
+
+    fn check_arithmetic_operands(
+        lhs: &Expr,
+        rhs: &Expr,
+        position: &Position,
+    ) -> anyhow::Result<()> {
+        if !lhs.ty.is_arithmetic() || !rhs.ty.is_arithmetic() {
+            return Err(anyhow!("{}Invalid operands to binary expression", position));
+        }
+        Ok(())
+    }
+
+    /// Brings arithmetic operands of a comparison to their common type. A pointer may only be
+    /// compared with a pointer or an integer.
+    fn convert_comparison_operands(
+        lhs: Expr,
+        rhs: Expr,
+        position: &Position,
+    ) -> anyhow::Result<(Expr, Expr)> {
+        Self::check_scalar_operands(&lhs, &rhs, position)?;
+        if lhs.ty.is_arithmetic() && rhs.ty.is_arithmetic() {
+            let ty = Type::common(&lhs.ty, &rhs.ty);
+            return Ok((Self::new_convert(lhs, &ty), Self::new_convert(rhs, &ty)));
+        }
+        if lhs.ty.is_float() || rhs.ty.is_float() {
+            return Err(anyhow!("{}Invalid operands to binary expression", position));
+        }
+        Ok((lhs, rhs))
+    }
+
+    /// Brings the second and third operands of `?:` to the type of the result, following
+    /// C11 6.5.15.
+    fn convert_condition_operands(
+        then_expr: Expr,
+        else_expr: Expr,
+        position: &Position,
+    ) -> anyhow::Result<(Expr, Expr, Type)> {
+        if then_expr.ty.is_arithmetic() && else_expr.ty.is_arithmetic() {
+            let ty = Type::common(&then_expr.ty, &else_expr.ty);
+            return Ok((
+                Self::new_convert(then_expr, &ty),
+                Self::new_convert(else_expr, &ty),
+                ty,
+            ));
+        }
+        let ty = match (then_expr.ty.pointee(), else_expr.ty.pointee()) {
+            (Some(then_pointee), Some(else_pointee)) => {
+                if then_pointee == else_pointee || *else_pointee == Type::Void {
+                    else_expr.ty.clone()
+                } else if *then_pointee == Type::Void {
+                    then_expr.ty.clone()
+                } else {
+                    return Err(anyhow!(
+                        "{}Pointer type mismatch in conditional expression",
+                        position
+                    ));
+                }
+            }
+            (Some(_), None) if Self::is_null_pointer_constant(&else_expr) => then_expr.ty.clone(),
+            (None, Some(_)) if Self::is_null_pointer_constant(&then_expr) => else_expr.ty.clone(),
+            (None, None) if then_expr.ty == else_expr.ty && !then_expr.ty.is_scalar() => {
+                then_expr.ty.clone()
+            }
+            _ => {
+                return Err(anyhow!(
+                    "{}Incompatible operand types in conditional expression",
+                    position
+                ))
+            }
+        };
+        // A null pointer constant is an integer until it is cast.
+        let convert = |expr: Expr| {
+            if expr.ty.is_integer() {
+                let position = expr.position.clone();
+                Expr::new_cast(expr, ty.clone(), position)
+            } else {
+                expr
+            }
+        };
+        Ok((convert(then_expr), convert(else_expr), ty))
+    }
+
+    /// Whether `expr` is an integer constant expression with the value 0, or one cast to
+    /// `void *`.
+    fn is_null_pointer_constant(expr: &Expr) -> bool {
+        match &expr.kind {
+            ExprKind::Cast { expr: inner } if expr.ty.pointee() == Some(&Type::Void) => {
+                Self::is_null_pointer_constant(inner)
+            }
+            _ => expr.ty.is_integer() && matches!(Self::eval_const_expr(expr), Ok(0)),
+        }
+    }
+
+    fn check_scalar_operands(lhs: &Expr, rhs: &Expr, position: &Position) -> anyhow::Result<()> {
+        if !lhs.ty.is_scalar() || !rhs.ty.is_scalar() {
+            return Err(anyhow!("{}Invalid operands to binary expression", position));
+        }
+        Ok(())
+    }
+
     fn analyze_expr_assign(
         &mut self,
         row_assign_op_kind: RowAssignOpKind,
@@ -139,6 +537,279 @@ impl Analyzer {
         let lhs = self.analyze_expr(row_lhs_expr)?;
         let rhs = self.analyze_expr(row_rhs_expr)?;
         let binary_op_kind = BinaryOpKind::from_row_assign_op_kind(row_assign_op_kind)?;
-        Ok(Expr::new_assign(binary_op_kind, lhs, rhs, position))
+        Self::new_assign(binary_op_kind, lhs, rhs, position)
+    }
+
+    pub fn new_assign(
+        op_kind: BinaryOpKind,
+        lhs: Expr,
+        rhs: Expr,
+        position: Position,
+    ) -> anyhow::Result<Expr> {
+        let rhs = match (&op_kind, lhs.ty.pointee()) {
+            (BinaryOpKind::Add | BinaryOpKind::Sub, Some(ty)) if rhs.ty.is_integer() => {
+                Self::new_scaled(rhs, ty.size())
+            }
+            (BinaryOpKind::Equal, _) => {
+                if !Self::is_assignable(&lhs.ty, &rhs) {
+                    return Err(anyhow!("{}Incompatible types in assignment", position));
+                }
+                Self::new_convert(rhs, &lhs.ty)
+            }
+            // The operation is done in the common type and the result converted back.
+            (BinaryOpKind::Add | BinaryOpKind::Sub | BinaryOpKind::Mul | BinaryOpKind::Div, _) => {
+                Self::check_arithmetic_operands(&lhs, &rhs, &position)?;
+                let ty = Type::common(&lhs.ty, &rhs.ty);
+                Self::new_convert(rhs, &ty)
+            }
+            (BinaryOpKind::LShift | BinaryOpKind::RShift, _) => {
+                Self::check_integer_operands(&lhs, &rhs, &position)?;
+                rhs
+            }
+            _ => {
+                Self::check_integer_operands(&lhs, &rhs, &position)?;
+                let ty = Type::common(&lhs.ty, &rhs.ty);
+                Self::new_convert(rhs, &ty)
+            }
+        };
+        let ty = lhs.ty.clone();
+        Ok(Expr::new_assign(op_kind, lhs, rhs, ty, position))
+    }
+
+    /// Whether `expr` may be converted to `ty` as if by assignment. Structs only go to the same
+    /// struct, and floating values and pointers do not convert to each other.
+    pub fn is_assignable(ty: &Type, expr: &Expr) -> bool {
+        !((ty.is_struct() || expr.ty.is_struct()) && *ty != expr.ty
+            || (ty.is_float() && expr.ty.is_pointer())
+            || (ty.is_pointer() && expr.ty.is_float()))
+    }
+
+    fn analyze_expr_func(
+        &mut self,
+        row_name_expr: RowExpr,
+        row_args_expr: Vec<RowExpr>,
+        position: Position,
+    ) -> anyhow::Result<Expr> {
+        let args = row_args_expr
+            .into_iter()
+            .map(|arg| self.analyze_expr(arg))
+            .collect::<anyhow::Result<Vec<Expr>>>()?;
+        if args.iter().any(|arg| arg.ty.is_struct()) {
+            return Err(anyhow!(
+                "{}Passing struct by value is not supported",
+                position
+            ));
+        }
+        // An identifier not declared as an object names a function, declared or not.
+        if let RowExprKind::Identifier { ident } = &row_name_expr.kind {
+            match self.get_symbol(ident, &position) {
+                Ok(Symbol::Variable { .. }) => {}
+                Ok(_) => return Err(anyhow!("{}Called object is not a function", position)),
+                Err(_) => {
+                    let ty =
+                        self.func.get(ident).cloned().unwrap_or_else(|| {
+                            Type::new_function(Type::new_int(), Vec::new(), true)
+                        });
+                    let (args, ret) = Self::convert_args(args, ty, Some(ident), &position)?;
+                    return Ok(Expr::new_func_label(ident.clone(), args, ret, position));
+                }
+            }
+        }
+        // Otherwise the callee is a pointer to a function, or a function designated through one.
+        let name = match &row_name_expr.kind {
+            RowExprKind::Identifier { ident } => Some(ident.clone()),
+            _ => None,
+        };
+        let expr = self.analyze_expr(row_name_expr)?;
+        let expr = match expr.kind {
+            ExprKind::Deref { expr: inner } if matches!(expr.ty, Type::Function { .. }) => *inner,
+            kind => Expr { kind, ..expr },
+        };
+        let Some(ty @ Type::Function { .. }) = expr.ty.pointee().cloned() else {
+            return Err(anyhow!("{}Called object is not a function", position));
+        };
+        let (args, ret) = Self::convert_args(args, ty, name.as_deref(), &position)?;
+        Ok(Expr::new_func_expr(expr, args, ret, position))
+    }
+
+    /// Checks the arguments of a call to a function of type `ty`, named `name` if called by name,
+    /// and converts them, returning them with the return type.
+    fn convert_args(
+        args: Vec<Expr>,
+        ty: Type,
+        name: Option<&str>,
+        position: &Position,
+    ) -> anyhow::Result<(Vec<Expr>, Type)> {
+        let Type::Function {
+            ret,
+            params,
+            variadic,
+        } = ty
+        else {
+            unreachable!()
+        };
+        let callee = match name {
+            Some(name) => format!("function `{}`", name),
+            None => "function call".to_string(),
+        };
+        if args.len() < params.len() {
+            return Err(anyhow!("{}Too few arguments to {}", position, callee));
+        }
+        if params.len() < args.len() && !variadic {
+            return Err(anyhow!("{}Too many arguments to {}", position, callee));
+        }
+        // Arguments are converted to the parameter types, and the rest get the default argument
+        // promotions.
+        let args = args
+            .into_iter()
+            .enumerate()
+            .map(|(index, arg)| match params.get(index) {
+                Some(param) if !Self::is_assignable(param, &arg) => Err(anyhow!(
+                    "{}Incompatible type for argument {} of {}",
+                    arg.position,
+                    index + 1,
+                    callee
+                )),
+                Some(param) => Ok(Self::new_convert(arg, param)),
+                None if arg.ty == Type::new_float(FloatKind::Float) => {
+                    Ok(Self::new_convert(arg, &Type::new_double()))
+                }
+                None => Ok(arg),
+            })
+            .collect::<anyhow::Result<Vec<Expr>>>()?;
+        Ok((args, *ret))
+    }
+
+    /// Types an integer constant as the first type of its list in C11 6.4.4.1 that can represent
+    /// its value. Decimal constants without a 'u' suffix only take signed types.
+    fn integer_constant_type(number: usize, format: IntegerFormat, position: &Position) -> Type {
+        let kinds = [IntegerKind::Int, IntegerKind::Long, IntegerKind::LongLong];
+        let signs: &[bool] = match (format.is_unsigned, format.is_decimal) {
+            (true, _) => &[false],
+            (false, true) => &[true],
+            (false, false) => &[true, false],
+        };
+        let candidates = kinds[format.longs..]
+            .iter()
+            .flat_map(|kind| signs.iter().map(|signed| Type::new_integer(*kind, *signed)));
+        for ty in candidates {
+            let bits = ty.size() * 8 - ty.is_signed() as usize;
+            if bits >= usize::BITS as usize || number < 1 << bits {
+                return ty;
+            }
+        }
+        eprintln!(
+            "{}Warning: integer constant is so large that it is unsigned",
+            position
+        );
+        Type::new_integer(IntegerKind::LongLong, false)
+    }
+
+    /// The element type of a string literal: `char`, `char16_t`, `char32_t` or `wchar_t`.
+    pub fn string_elem_type(encoding: Encoding) -> Type {
+        match encoding {
+            Encoding::Char | Encoding::Utf8 => Type::new_integer(IntegerKind::Char, true),
+            Encoding::Char16 => Type::new_integer(IntegerKind::Short, false),
+            Encoding::Char32 => Type::new_integer(IntegerKind::Int, false),
+            Encoding::Wide => Type::new_int(),
+        }
+    }
+
+    /// Emits a string literal as an anonymous array in `.rodata`.
+    fn new_string_literal(&mut self, encoding: Encoding, units: Vec<u32>) -> Variable {
+        let label = format!(".L.str.{}", self.string_count);
+        self.string_count += 1;
+        let ty = Type::new_array(Self::string_elem_type(encoding), units.len() + 1);
+        let mut init = GlobalInit::new(ty.size());
+        let size = encoding.unit_size();
+        for (index, unit) in units.iter().enumerate() {
+            init.bytes[index * size..(index + 1) * size]
+                .copy_from_slice(&unit.to_le_bytes()[..size]);
+        }
+        let global = GlobalVariable::new(label.clone(), ty.clone(), Some(init), true, true, false);
+        self.globals.push(global);
+        Variable::new_global(label, ty)
+    }
+
+    fn new_sizeof(ty: &Type, position: Position) -> anyhow::Result<Expr> {
+        if ty.size() == 0 {
+            return Err(anyhow!(
+                "{}Invalid application of `sizeof` to an incomplete type",
+                position
+            ));
+        }
+        Ok(Expr::new_number(
+            ty.size(),
+            Type::new_unsigned_long(),
+            position,
+        ))
+    }
+
+    fn decay(expr: Expr) -> Expr {
+        if !expr.ty.is_array() {
+            return expr;
+        }
+        let ty = expr.ty.decay();
+        let position = expr.position.clone();
+        Expr::new_addr(expr, ty, position)
+    }
+
+    fn new_truth_value(expr: Expr) -> Expr {
+        let position = expr.position.clone();
+        let zero = Self::new_convert(
+            Expr::new_number(0, Type::new_int(), position.clone()),
+            &expr.ty,
+        );
+        Expr::new_binary(
+            BinaryOpKind::NotEqual,
+            expr,
+            zero,
+            Type::new_int(),
+            position,
+        )
+    }
+
+    /// Checks that a controlling expression is scalar, and compares a floating one with zero,
+    /// since the generator tests conditions in a general-purpose register.
+    pub fn new_condition_value(expr: Expr) -> anyhow::Result<Expr> {
+        if !expr.ty.is_scalar() {
+            return Err(anyhow!(
+                "{}Expression of scalar type is required",
+                expr.position
+            ));
+        }
+        Ok(if expr.ty.is_float() {
+            Self::new_truth_value(expr)
+        } else {
+            expr
+        })
+    }
+
+    /// Converts an arithmetic value to `ty` as if by assignment, so that the generator sees the
+    /// operands in the type the operation is done in. Constants are converted in place.
+    pub fn new_convert(expr: Expr, ty: &Type) -> Expr {
+        if expr.ty == *ty || !(expr.ty.is_arithmetic() && ty.is_arithmetic()) {
+            return expr;
+        }
+        let position = expr.position.clone();
+        match &expr.kind {
+            ExprKind::Number { number } if ty.is_integer() => {
+                let value = Self::convert_const(*number as i64, &expr.ty);
+                Expr::new_number(
+                    Self::convert_const(value, ty) as usize,
+                    ty.clone(),
+                    position,
+                )
+            }
+            ExprKind::Number { number } if ty.is_float() => {
+                let value = Self::convert_const(*number as i64, &expr.ty);
+                let value = FloatValue::from_integer(value, expr.ty.is_signed());
+                Expr::new_float_number(Self::round_float(value, ty), ty.clone(), position)
+            }
+            ExprKind::FloatNumber { value } if ty.is_float() => {
+                Expr::new_float_number(Self::round_float(*value, ty), ty.clone(), position)
+            }
+            _ => Expr::new_cast(expr, ty.clone(), position),
+        }
     }
 }
@@ -0,0 +1,76 @@
+use std::collections::BTreeSet;
+
+use anyhow::anyhow;
+
+use crate::{
+    analyze::{function::Function, stmt::Stmt, types::Type},
+    parse::{
+        row_decl::{RowDerived, RowStorageClass},
+        row_function::RowFunction,
+        row_stmt::RowStmtKind,
+    },
+};
+
+use super::{Analyzer, Linkage, Scope};
+
+impl Analyzer {
+    pub fn analyze_function(&mut self, row_function: RowFunction) -> anyhow::Result<Function> {
+        let storage_class = &row_function.decl_spec.storage_class;
+        if let Some(RowStorageClass::Typedef | RowStorageClass::Auto) = storage_class {
+            return Err(anyhow!(
+                "{}Invalid storage class for function definition",
+                row_function.position
+            ));
+        }
+        let ty = self.resolve_type(&row_function.decl_spec, &row_function.declarator)?;
+        let name = Self::declarator_name(&row_function.declarator)?;
+        let Type::Function { ret, .. } = &ty else {
+            unreachable!()
+        };
+        self.ret = *ret.clone();
+        let linkage = self.declare_linkage(&name, storage_class, true, &row_function.position)?;
+        self.declare_func(name.clone(), ty, &row_function.position)?;
+
+        self.scopes.push(Scope::default());
+        self.offset = 0;
+        self.max_offset = 0;
+        self.labels = BTreeSet::new();
+        self.gotos = Vec::new();
+        let mut params = Vec::new();
+        if let Some(RowDerived::Function {
+            params: row_params, ..
+        }) = row_function.declarator.derived.last()
+        {
+            for row_param in row_params.iter() {
+                let ty = self.resolve_param_type(&row_param.decl_spec, &row_param.declarator)?;
+                let Some(param_name) = row_param.declarator.name.clone() else {
+                    return Err(anyhow!(
+                        "{}Parameter name omitted",
+                        row_param.declarator.position
+                    ));
+                };
+                params.push(self.declare_var(param_name, ty, &row_param.declarator.position)?);
+            }
+        }
+        // Parameters share the scope of the outermost block.
+        let RowStmtKind::Cpd { stmts } = row_function.body.kind else {
+            unreachable!()
+        };
+        let stmts = self.analyze_block_items(stmts);
+        self.scopes.pop();
+        let body = Stmt::new_cpd(stmts?, row_function.body.position);
+        for (label, position) in self.gotos.iter() {
+            if !self.labels.contains(label) {
+                return Err(anyhow!("{}Use of undeclared label `{}`", position, label));
+            }
+        }
+        let is_static = linkage == Linkage::Internal;
+        Ok(Function::new(
+            name,
+            params,
+            body,
+            self.max_offset,
+            is_static,
+        ))
+    }
+}
@@ -0,0 +1,459 @@
+use std::{iter::Peekable, vec::IntoIter};
+
+use anyhow::anyhow;
+
+use crate::{
+    analyze::{
+        expr::{BinaryOpKind, Expr},
+        types::{Member, StructType, Type},
+        variable::Variable,
+    },
+    file::position::Position,
+    parse::{
+        row_decl::{RowDesignator, RowInitItem, RowInitializer},
+        row_expr::RowExprKind,
+    },
+};
+
+use super::Analyzer;
+
+type Items = Peekable<IntoIter<InitItem>>;
+
+/// An item of an initializer list. A bare expression is kept once it has been analyzed, so that
+/// brace elision does not analyze it again.
+struct InitItem {
+    designators: Vec<RowDesignator>,
+    init: Init,
+}
+
+enum Init {
+    Row(RowInitializer),
+    Expr(Expr),
+}
+
+impl Init {
+    fn position(&self) -> &Position {
+        match self {
+            Self::Row(row_init) => row_init.position(),
+            Self::Expr(expr) => &expr.position,
+        }
+    }
+}
+
+/// A value stored at a byte offset of the initialized object.
+#[derive(Debug)]
+pub struct InitElem {
+    pub offset: usize,
+    pub ty: Type,
+    pub expr: Expr,
+}
+
+impl Analyzer {
+    /// Flattens the initializer of an object of type `ty` into the values it stores. An array of
+    /// unknown length takes its length from the initializer.
+    pub fn analyze_initializer(
+        &mut self,
+        ty: Type,
+        row_init: RowInitializer,
+    ) -> anyhow::Result<(Type, Vec<InitElem>)> {
+        let mut elems = Vec::new();
+        let ty = match (ty, row_init) {
+            (Type::Array { ty: elem, len: 0 }, row_init)
+                if Self::string_literal(&elem, &row_init).is_some() =>
+            {
+                let len = Self::string_literal(&elem, &row_init).unwrap().len() + 1;
+                let ty = Type::new_array(*elem, len);
+                self.init_object(&ty, 0, Init::Row(row_init), &mut elems)?;
+                ty
+            }
+            (Type::Array { ty: elem, len: 0 }, RowInitializer::List { items, .. }) => {
+                let mut items = Self::init_items(items);
+                let len = self.init_array(&elem, None, 0, &mut items, &mut elems, true)?;
+                Type::new_array(*elem, len)
+            }
+            (ty, row_init) => {
+                self.init_object(&ty, 0, Init::Row(row_init), &mut elems)?;
+                ty
+            }
+        };
+        Ok((ty, elems))
+    }
+
+    fn init_items(items: Vec<RowInitItem>) -> Items {
+        items
+            .into_iter()
+            .map(|item| InitItem {
+                designators: item.designators,
+                init: Init::Row(item.init),
+            })
+            .collect::<Vec<_>>()
+            .into_iter()
+            .peekable()
+    }
+
+    /// Initializes one object from one initializer.
+    fn init_object(
+        &mut self,
+        ty: &Type,
+        offset: usize,
+        init: Init,
+        elems: &mut Vec<InitElem>,
+    ) -> anyhow::Result<()> {
+        let row_init = match init {
+            Init::Row(row_init) => row_init,
+            Init::Expr(expr) => return Self::init_scalar(ty, offset, expr, elems),
+        };
+        if let Type::Array { ty: elem, len } = ty {
+            if let Some(units) = Self::string_literal(elem, &row_init) {
+                if units.len() > *len {
+                    return Err(anyhow!(
+                        "{}Initializer-string for array is too long",
+                        row_init.position()
+                    ));
+                }
+                // The null character is dropped if the array has no room for it.
+                for (index, unit) in units.iter().enumerate() {
+                    let expr = Expr::new_number(
+                        *unit as usize,
+                        Type::new_long(),
+                        row_init.position().clone(),
+                    );
+                    elems.push(InitElem {
+                        offset: offset + index * elem.size(),
+                        ty: *elem.clone(),
+                        expr,
+                    });
+                }
+                return Ok(());
+            }
+        }
+        match row_init {
+            RowInitializer::List { items, position } => {
+                if !ty.is_array() && !ty.is_struct() {
+                    let mut items = items.into_iter();
+                    return match (items.next(), items.next()) {
+                        (Some(item), None) if item.designators.is_empty() => {
+                            self.init_object(ty, offset, Init::Row(item.init), elems)
+                        }
+                        (None, _) => Err(anyhow!("{}Empty scalar initializer", position)),
+                        (Some(item), _) if !item.designators.is_empty() => Err(anyhow!(
+                            "{}Designator in initializer for scalar type",
+                            Self::designator_position(&item.designators[0])
+                        )),
+                        (Some(item), _) => Err(anyhow!(
+                            "{}Excess elements in scalar initializer",
+                            item.init.position()
+                        )),
+                    };
+                }
+                let mut items = Self::init_items(items);
+                self.init_aggregate(ty, offset, &mut items, elems, true)
+            }
+            RowInitializer::Expr { expr } => {
+                if ty.is_array() && matches!(expr.kind, RowExprKind::Str { .. }) {
+                    return Err(anyhow!(
+                        "{}Array of inappropriate type initialized from string literal",
+                        expr.position
+                    ));
+                }
+                let expr = self.analyze_expr(expr)?;
+                Self::init_scalar(ty, offset, expr, elems)
+            }
+        }
+    }
+
+    /// Initializes an object, which is not an array, from an analyzed expression.
+    fn init_scalar(
+        ty: &Type,
+        offset: usize,
+        expr: Expr,
+        elems: &mut Vec<InitElem>,
+    ) -> anyhow::Result<()> {
+        if ty.is_array() {
+            return Err(anyhow!(
+                "{}Array initializer must be an initializer list",
+                expr.position
+            ));
+        }
+        elems.push(InitElem {
+            offset,
+            ty: ty.clone(),
+            expr,
+        });
+        Ok(())
+    }
+
+    /// Initializes the elements or members of an aggregate from `items`. Without braces, only as
+    /// many items are taken as the aggregate needs.
+    fn init_aggregate(
+        &mut self,
+        ty: &Type,
+        offset: usize,
+        items: &mut Items,
+        elems: &mut Vec<InitElem>,
+        braced: bool,
+    ) -> anyhow::Result<()> {
+        match ty {
+            Type::Array { ty: elem, len } => {
+                self.init_array(elem, Some(*len), offset, items, elems, braced)?;
+            }
+            Type::Struct { struct_type } => {
+                self.init_struct(struct_type, offset, items, elems, braced)?;
+            }
+            _ => unreachable!(),
+        }
+        Ok(())
+    }
+
+    /// Returns the number of elements initialized.
+    fn init_array(
+        &mut self,
+        elem: &Type,
+        len: Option<usize>,
+        offset: usize,
+        items: &mut Items,
+        elems: &mut Vec<InitElem>,
+        braced: bool,
+    ) -> anyhow::Result<usize> {
+        let mut index = 0;
+        let mut max_len = 0;
+        while let Some(item) = items.peek() {
+            if !item.designators.is_empty() {
+                if !braced {
+                    break;
+                }
+                let item = items.next().unwrap();
+                let mut designators = item.designators.into_iter();
+                let designator = designators.next().unwrap();
+                index = self.eval_index_designator(designator, len)?;
+                let offset = offset + index * elem.size();
+                self.init_designated(elem, offset, designators.collect(), item.init, elems)?;
+            } else {
+                if len.is_some_and(|len| index >= len) {
+                    if braced {
+                        return Err(anyhow!(
+                            "{}Excess elements in array initializer",
+                            item.init.position()
+                        ));
+                    }
+                    break;
+                }
+                self.init_member(elem, offset + index * elem.size(), items, elems)?;
+            }
+            index += 1;
+            max_len = max_len.max(index);
+        }
+        Ok(max_len)
+    }
+
+    fn init_struct(
+        &mut self,
+        struct_type: &StructType,
+        offset: usize,
+        items: &mut Items,
+        elems: &mut Vec<InitElem>,
+        braced: bool,
+    ) -> anyhow::Result<()> {
+        let members = struct_type.members();
+        let mut index = 0;
+        while let Some(item) = items.peek() {
+            if !item.designators.is_empty() {
+                if !braced {
+                    break;
+                }
+                let item = items.next().unwrap();
+                let mut designators = item.designators.into_iter();
+                let designator = designators.next().unwrap();
+                let (member_index, mut rest) = Self::find_member_designator(&members, designator)?;
+                rest.extend(designators);
+                let member = &members[member_index];
+                let offset = offset + member.offset;
+                self.init_designated(&member.ty, offset, rest, item.init, elems)?;
+                index = member_index + 1;
+            } else {
+                if index >= members.len() || (struct_type.is_union && index > 0) {
+                    if braced {
+                        return Err(anyhow!(
+                            "{}Excess elements in {:?} initializer",
+                            item.init.position(),
+                            struct_type
+                        ));
+                    }
+                    break;
+                }
+                let member = &members[index];
+                self.init_member(&member.ty, offset + member.offset, items, elems)?;
+                index += 1;
+            }
+        }
+        Ok(())
+    }
+
+    /// Initializes a sub-object from the next item, eliding its braces if the item is a bare
+    /// expression that does not initialize the whole sub-object.
+    fn init_member(
+        &mut self,
+        ty: &Type,
+        offset: usize,
+        items: &mut Items,
+        elems: &mut Vec<InitElem>,
+    ) -> anyhow::Result<()> {
+        let item = items.peek_mut().unwrap();
+        // A struct is initialized whole by an expression of its type, so the expression is
+        // analyzed here and kept for the member it initializes otherwise. A string literal only
+        // ever initializes an array.
+        if ty.is_struct() {
+            if let Init::Row(RowInitializer::Expr { expr }) = &item.init {
+                if !matches!(expr.kind, RowExprKind::Str { .. }) {
+                    let expr = self.analyze_expr(expr.clone())?;
+                    item.init = Init::Expr(expr);
+                }
+            }
+        }
+        let is_elided = match &item.init {
+            Init::Row(RowInitializer::List { .. }) => false,
+            Init::Row(row_init @ RowInitializer::Expr { .. }) => match ty {
+                Type::Array { ty: elem, .. } => Self::string_literal(elem, row_init).is_none(),
+                _ => ty.is_struct(),
+            },
+            Init::Expr(expr) => !ty.is_scalar() && expr.ty != *ty,
+        };
+        if is_elided {
+            return self.init_aggregate(ty, offset, items, elems, false);
+        }
+        let item = items.next().unwrap();
+        self.init_object(ty, offset, item.init, elems)
+    }
+
+    fn init_designated(
+        &mut self,
+        ty: &Type,
+        offset: usize,
+        designators: Vec<RowDesignator>,
+        init: Init,
+        elems: &mut Vec<InitElem>,
+    ) -> anyhow::Result<()> {
+        let mut designators = designators.into_iter();
+        let Some(designator) = designators.next() else {
+            let item = InitItem {
+                designators: Vec::new(),
+                init,
+            };
+            let mut items = vec![item].into_iter().peekable();
+            return self.init_member(ty, offset, &mut items, elems);
+        };
+        match ty {
+            Type::Array { ty: elem, len } => {
+                let index = self.eval_index_designator(designator, Some(*len))?;
+                let offset = offset + index * elem.size();
+                self.init_designated(elem, offset, designators.collect(), init, elems)
+            }
+            Type::Struct { struct_type } => {
+                let members = struct_type.members();
+                let (index, mut rest) = Self::find_member_designator(&members, designator)?;
+                rest.extend(designators);
+                let member = &members[index];
+                let offset = offset + member.offset;
+                self.init_designated(&member.ty, offset, rest, init, elems)
+            }
+            _ => Err(anyhow!(
+                "{}Designator in initializer for scalar type",
+                Self::designator_position(&designator)
+            )),
+        }
+    }
+
+    /// Returns the code units of a string literal initializing an array of `elem`, which may be
+    /// enclosed in braces.
+    fn string_literal<'a>(elem: &Type, row_init: &'a RowInitializer) -> Option<&'a [u32]> {
+        let expr = match row_init {
+            RowInitializer::Expr { expr } => expr,
+            RowInitializer::List { items, .. } => match items.as_slice() {
+                [item] if item.designators.is_empty() => match &item.init {
+                    RowInitializer::Expr { expr } => expr,
+                    RowInitializer::List { .. } => return None,
+                },
+                _ => return None,
+            },
+        };
+        match &expr.kind {
+            RowExprKind::Str { encoding, units }
+                if elem.is_integer() && elem.size() == encoding.unit_size() =>
+            {
+                Some(units)
+            }
+            _ => None,
+        }
+    }
+
+    fn eval_index_designator(
+        &mut self,
+        designator: RowDesignator,
+        len: Option<usize>,
+    ) -> anyhow::Result<usize> {
+        let RowDesignator::Index { expr, position } = designator else {
+            return Err(anyhow!(
+                "{}Field designator used for non-struct type",
+                Self::designator_position(&designator)
+            ));
+        };
+        let expr = self.analyze_expr(expr)?;
+        let index = Self::eval_const_expr(&expr)?;
+        if index < 0 || len.is_some_and(|len| index as usize >= len) {
+            return Err(anyhow!(
+                "{}Array index in initializer exceeds array bounds",
+                position
+            ));
+        }
+        Ok(index as usize)
+    }
+
+    /// Finds the member a field designator names. A member of an anonymous struct or union is
+    /// reached through that anonymous member, so the designators to apply to it are returned too.
+    fn find_member_designator(
+        members: &[Member],
+        designator: RowDesignator,
+    ) -> anyhow::Result<(usize, Vec<RowDesignator>)> {
+        let RowDesignator::Member { name, position } = designator else {
+            return Err(anyhow!(
+                "{}Array designator used for non-array type",
+                Self::designator_position(&designator)
+            ));
+        };
+        for (index, member) in members.iter().enumerate() {
+            match (&member.name, &member.ty) {
+                (Some(member_name), _) if *member_name == name => {
+                    return Ok((index, Vec::new()));
+                }
+                (None, Type::Struct { struct_type })
+                    if struct_type.find_member(&name).is_some() =>
+                {
+                    return Ok((index, vec![RowDesignator::Member { name, position }]));
+                }
+                _ => {}
+            }
+        }
+        Err(anyhow!("{}No member named `{}`", position, name))
+    }
+
+    /// Builds the lvalue a value of a local initializer is stored to.
+    pub fn new_init_target(var: &Variable, elem: &InitElem, position: &Position) -> Expr {
+        let expr = Expr::new_var(var.clone(), position.clone());
+        if elem.offset == 0 && elem.ty == var.ty {
+            return expr;
+        }
+        let ty = Type::new_pointer(elem.ty.clone());
+        let addr = Expr::new_addr(expr, ty.clone(), position.clone());
+        let offset = Expr::new_number(elem.offset, Type::new_long(), position.clone());
+        let addr = Expr::new_binary(BinaryOpKind::Add, addr, offset, ty, position.clone());
+        Expr::new_deref(addr, elem.ty.clone(), position.clone())
+    }
+
+    fn designator_position(designator: &RowDesignator) -> &Position {
+        match designator {
+            RowDesignator::Index { position, .. } | RowDesignator::Member { position, .. } => {
+                position
+            }
+        }
+    }
+}
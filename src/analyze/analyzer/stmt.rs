@@ -1,5 +1,7 @@
+use anyhow::anyhow;
+
 use crate::{
-    analyze::{expr::Expr, stmt::Stmt},
+    analyze::{expr::Expr, stmt::Stmt, types::Type},
     file::position::Position,
     parse::{
         row_expr::RowExpr,
@@ -7,7 +9,7 @@ use crate::{
     },
 };
 
-use super::Analyzer;
+use super::{Analyzer, Scope, Switch};
 
 impl Analyzer {
     pub fn analyze_stmt(&mut self, row_stmt: RowStmt) -> anyhow::Result<Stmt> {
@@ -32,7 +34,55 @@ impl Analyzer {
                 condition_expr,
                 run_stmt,
             } => self.analyze_stmt_while(condition_expr, *run_stmt, position)?,
+            RowStmtKind::DoWhile {
+                run_stmt,
+                condition_expr,
+            } => self.analyze_stmt_do_while(*run_stmt, condition_expr, position)?,
+            RowStmtKind::Break => {
+                if self.loop_depth == 0 && self.switches.is_empty() {
+                    return Err(anyhow!(
+                        "{}`break` statement not in loop or switch statement",
+                        position
+                    ));
+                }
+                Stmt::new_break(position)
+            }
+            RowStmtKind::Continue => {
+                if self.loop_depth == 0 {
+                    return Err(anyhow!("{}`continue` statement not in loop", position));
+                }
+                Stmt::new_continue(position)
+            }
+            RowStmtKind::Switch {
+                condition_expr,
+                run_stmt,
+            } => self.analyze_stmt_switch(condition_expr, *run_stmt, position)?,
+            RowStmtKind::Case { expr, stmt } => self.analyze_stmt_case(expr, *stmt, position)?,
+            RowStmtKind::Default { stmt } => {
+                let Some(switch) = self.switches.last_mut() else {
+                    return Err(anyhow!(
+                        "{}`default` statement not in switch statement",
+                        position
+                    ));
+                };
+                if switch.has_default {
+                    return Err(anyhow!("{}Multiple default labels in one switch", position));
+                }
+                switch.has_default = true;
+                Stmt::new_default(self.analyze_stmt(*stmt)?, position)
+            }
+            RowStmtKind::Goto { label } => {
+                self.gotos.push((label.clone(), position.clone()));
+                Stmt::new_goto(label, position)
+            }
+            RowStmtKind::Label { label, stmt } => {
+                if !self.labels.insert(label.clone()) {
+                    return Err(anyhow!("{}Redefinition of label `{}`", position, label));
+                }
+                Stmt::new_label(label, self.analyze_stmt(*stmt)?, position)
+            }
             RowStmtKind::Cpd { stmts } => self.analyze_stmt_cpd(stmts, position)?,
+            RowStmtKind::Decl { decl } => self.analyze_decl(decl, position)?,
         })
     }
 
@@ -42,7 +92,7 @@ impl Analyzer {
         position: Position,
     ) -> anyhow::Result<Stmt> {
         let expr = if let Some(row_expr) = row_expr {
-            Some(self.analyze_expr(row_expr)?)
+            Some(self.analyze_expr_or_void(row_expr)?)
         } else {
             None
         };
@@ -55,7 +105,14 @@ impl Analyzer {
         position: Position,
     ) -> anyhow::Result<Stmt> {
         let expr = if let Some(row_expr) = row_expr {
-            Some(self.analyze_expr(row_expr)?)
+            let expr = self.analyze_expr(row_expr)?;
+            if !Self::is_assignable(&self.ret, &expr) {
+                return Err(anyhow!(
+                    "{}Incompatible types in return statement",
+                    expr.position
+                ));
+            }
+            Some(Self::new_convert(expr, &self.ret))
         } else {
             None
         };
@@ -69,7 +126,7 @@ impl Analyzer {
         row_else_stmt: Option<RowStmt>,
         position: Position,
     ) -> anyhow::Result<Stmt> {
-        let condition_expr = self.analyze_expr(row_condition_expr)?;
+        let condition_expr = Self::new_condition_value(self.analyze_expr(row_condition_expr)?)?;
         let then_stmt = self.analyze_stmt(row_then_stmt)?;
         let else_stmt = if let Some(row_else_stmt) = row_else_stmt {
             Some(self.analyze_stmt(row_else_stmt)?)
@@ -88,21 +145,21 @@ impl Analyzer {
         position: Position,
     ) -> anyhow::Result<Stmt> {
         let init_expr = if let Some(row_init_expr) = row_init_expr {
-            Some(self.analyze_expr(row_init_expr)?)
+            Some(self.analyze_expr_or_void(row_init_expr)?)
         } else {
             None
         };
         let condition_expr = if let Some(row_condition_expr) = row_condition_expr {
-            self.analyze_expr(row_condition_expr)?
+            Self::new_condition_value(self.analyze_expr(row_condition_expr)?)?
         } else {
-            Expr::new_number(1, position.clone())
+            Expr::new_number(1, Type::new_int(), position.clone())
         };
         let delta_expr = if let Some(row_delta_expr) = row_delta_expr {
-            Some(self.analyze_expr(row_delta_expr)?)
+            Some(self.analyze_expr_or_void(row_delta_expr)?)
         } else {
             None
         };
-        let run_stmt = self.analyze_stmt(row_run_stmt)?;
+        let run_stmt = self.analyze_loop_body(row_run_stmt)?;
         Ok(Stmt::new_for(
             init_expr,
             condition_expr,
@@ -118,20 +175,105 @@ impl Analyzer {
         row_run_stmt: RowStmt,
         position: Position,
     ) -> anyhow::Result<Stmt> {
-        let condition_expr = self.analyze_expr(row_condition_expr)?;
-        let run_stmt = self.analyze_stmt(row_run_stmt)?;
+        let condition_expr = Self::new_condition_value(self.analyze_expr(row_condition_expr)?)?;
+        let run_stmt = self.analyze_loop_body(row_run_stmt)?;
         Ok(Stmt::new_while(condition_expr, run_stmt, position))
     }
 
+    fn analyze_stmt_do_while(
+        &mut self,
+        row_run_stmt: RowStmt,
+        row_condition_expr: RowExpr,
+        position: Position,
+    ) -> anyhow::Result<Stmt> {
+        let run_stmt = self.analyze_loop_body(row_run_stmt)?;
+        let condition_expr = Self::new_condition_value(self.analyze_expr(row_condition_expr)?)?;
+        Ok(Stmt::new_do_while(run_stmt, condition_expr, position))
+    }
+
+    fn analyze_stmt_switch(
+        &mut self,
+        row_condition_expr: RowExpr,
+        row_run_stmt: RowStmt,
+        position: Position,
+    ) -> anyhow::Result<Stmt> {
+        let condition_expr = self.analyze_expr(row_condition_expr)?;
+        if !condition_expr.ty.is_integer() {
+            return Err(anyhow!(
+                "{}Statement requires expression of integer type",
+                condition_expr.position
+            ));
+        }
+        let ty = condition_expr.ty.promote();
+        self.switches.push(Switch {
+            ty,
+            cases: Vec::new(),
+            has_default: false,
+        });
+        let run_stmt = self.analyze_stmt(row_run_stmt);
+        let switch = self.switches.pop().unwrap();
+        Ok(Stmt::new_switch(
+            condition_expr,
+            run_stmt?,
+            switch.cases,
+            switch.has_default,
+            position,
+        ))
+    }
+
+    fn analyze_stmt_case(
+        &mut self,
+        row_expr: RowExpr,
+        row_stmt: RowStmt,
+        position: Position,
+    ) -> anyhow::Result<Stmt> {
+        let expr = self.analyze_expr(row_expr)?;
+        let value = Self::eval_const_expr(&expr)?;
+        let Some(switch) = self.switches.last_mut() else {
+            return Err(anyhow!(
+                "{}`case` statement not in switch statement",
+                position
+            ));
+        };
+        let value = Self::convert_const(value, &switch.ty);
+        if switch.cases.contains(&value) {
+            return Err(anyhow!("{}Duplicate case value `{}`", expr.position, value));
+        }
+        switch.cases.push(value);
+        let index = switch.cases.len() - 1;
+        Ok(Stmt::new_case(
+            index,
+            self.analyze_stmt(row_stmt)?,
+            position,
+        ))
+    }
+
+    fn analyze_loop_body(&mut self, row_run_stmt: RowStmt) -> anyhow::Result<Stmt> {
+        self.loop_depth += 1;
+        let run_stmt = self.analyze_stmt(row_run_stmt);
+        self.loop_depth -= 1;
+        run_stmt
+    }
+
     fn analyze_stmt_cpd(
         &mut self,
         stmts: Vec<RowStmt>,
         position: Position,
     ) -> anyhow::Result<Stmt> {
-        let stmts = stmts
+        // Sibling blocks reuse the stack slots of the variables declared here.
+        let offset = self.offset;
+        self.scopes.push(Scope::default());
+        let stmts = self.analyze_block_items(stmts);
+        self.scopes.pop();
+        self.offset = offset;
+        Ok(Stmt::new_cpd(stmts?, position))
+    }
+
+    /// Analyzes the items of a block in the current scope.
+    pub fn analyze_block_items(&mut self, stmts: Vec<RowStmt>) -> anyhow::Result<Vec<Stmt>> {
+        stmts
             .into_iter()
             .map(|stmt| self.analyze_stmt(stmt))
-            .collect::<anyhow::Result<Vec<Stmt>>>()?;
-        Ok(Stmt::new_cpd(stmts, position))
+            .collect()
     }
 }
@@ -5,61 +5,134 @@ use crate::{
     parse::row_expr::{RowAssignOpKind, RowBinaryOpKind},
 };
 
-use super::variable::Variable;
+use super::{float_value::FloatValue, types::Type, variable::Variable};
 
 #[derive(Debug, Clone)]
 pub struct Expr {
     pub kind: ExprKind,
+    pub ty: Type,
     pub position: Position,
 }
 
 impl Expr {
-    pub fn new_binary(op_kind: BinaryOpKind, lhs: Expr, rhs: Expr, position: Position) -> Self {
+    pub fn new_binary(
+        op_kind: BinaryOpKind,
+        lhs: Expr,
+        rhs: Expr,
+        ty: Type,
+        position: Position,
+    ) -> Self {
         Self {
             kind: ExprKind::Binary {
                 op_kind,
                 lhs: Box::new(lhs),
                 rhs: Box::new(rhs),
             },
+            ty,
             position,
         }
     }
 
-    pub fn new_assign(op_kind: BinaryOpKind, lhs: Expr, rhs: Expr, position: Position) -> Self {
+    pub fn new_assign(
+        op_kind: BinaryOpKind,
+        lhs: Expr,
+        rhs: Expr,
+        ty: Type,
+        position: Position,
+    ) -> Self {
         Self {
             kind: ExprKind::Assign {
                 op_kind,
                 lhs: Box::new(lhs),
                 rhs: Box::new(rhs),
             },
+            ty,
+            position,
+        }
+    }
+
+    pub fn new_unary(op_kind: UnaryOpKind, expr: Expr, ty: Type, position: Position) -> Self {
+        Self {
+            kind: ExprKind::Unary {
+                op_kind,
+                expr: Box::new(expr),
+            },
+            ty,
+            position,
+        }
+    }
+
+    pub fn new_addr(expr: Expr, ty: Type, position: Position) -> Self {
+        Self {
+            kind: ExprKind::Addr {
+                expr: Box::new(expr),
+            },
+            ty,
+            position,
+        }
+    }
+
+    pub fn new_deref(expr: Expr, ty: Type, position: Position) -> Self {
+        Self {
+            kind: ExprKind::Deref {
+                expr: Box::new(expr),
+            },
+            ty,
+            position,
+        }
+    }
+
+    pub fn new_member(expr: Expr, offset: usize, ty: Type, position: Position) -> Self {
+        Self {
+            kind: ExprKind::Member {
+                expr: Box::new(expr),
+                offset,
+            },
+            ty,
+            position,
+        }
+    }
+
+    pub fn new_cast(expr: Expr, ty: Type, position: Position) -> Self {
+        Self {
+            kind: ExprKind::Cast {
+                expr: Box::new(expr),
+            },
+            ty,
             position,
         }
     }
 
     pub fn new_postfix_increment(expr: Expr, position: Position) -> Self {
+        let ty = expr.ty.clone();
         Self {
             kind: ExprKind::PostfixIncrement {
                 expr: Box::new(expr),
             },
+            ty,
             position,
         }
     }
 
     pub fn new_postfix_decrement(expr: Expr, position: Position) -> Self {
+        let ty = expr.ty.clone();
         Self {
             kind: ExprKind::PostfixDecrement {
                 expr: Box::new(expr),
             },
+            ty,
             position,
         }
     }
 
     pub fn new_comma(lhs: Expr, rhs: Expr, position: Position) -> Self {
+        let ty = rhs.ty.clone();
         Self {
             kind: ExprKind::Comma {
                 lhs: Box::new(lhs),
                 rhs: Box::new(rhs),
             },
+            ty,
             position,
         }
     }
@@ -68,6 +141,7 @@ impl Expr {
         condition: Expr,
         then_expr: Expr,
         else_expr: Expr,
+        ty: Type,
         position: Position,
     ) -> Self {
         Self {
@@ -76,25 +150,37 @@ impl Expr {
                 then_expr: Box::new(then_expr),
                 else_expr: Box::new(else_expr),
             },
+            ty,
             position,
         }
     }
 
     pub fn new_var(var: Variable, position: Position) -> Self {
+        let ty = var.ty.clone();
         Self {
             kind: ExprKind::Variable { var },
+            ty,
             position,
         }
     }
 
-    pub fn new_number(number: usize, position: Position) -> Self {
+    pub fn new_number(number: usize, ty: Type, position: Position) -> Self {
         Self {
             kind: ExprKind::Number { number },
+            ty,
+            position,
+        }
+    }
+
+    pub fn new_float_number(value: FloatValue, ty: Type, position: Position) -> Self {
+        Self {
+            kind: ExprKind::FloatNumber { value },
+            ty,
             position,
         }
     }
 
-    pub fn new_func_expr(expr: Expr, args: Vec<Expr>, position: Position) -> Self {
+    pub fn new_func_expr(expr: Expr, args: Vec<Expr>, ty: Type, position: Position) -> Self {
         Self {
             kind: ExprKind::Func {
                 name: FuncCallKind::Expr {
@@ -102,16 +188,18 @@ impl Expr {
                 },
                 args,
             },
+            ty,
             position,
         }
     }
 
-    pub fn new_func_label(name: String, args: Vec<Expr>, position: Position) -> Self {
+    pub fn new_func_label(name: String, args: Vec<Expr>, ty: Type, position: Position) -> Self {
         Self {
             kind: ExprKind::Func {
                 name: FuncCallKind::Label { name },
                 args,
             },
+            ty,
             position,
         }
     }
@@ -138,6 +226,23 @@ pub enum ExprKind {
         then_expr: Box<Expr>,
         else_expr: Box<Expr>,
     },
+    Unary {
+        op_kind: UnaryOpKind,
+        expr: Box<Expr>,
+    },
+    Addr {
+        expr: Box<Expr>,
+    },
+    Deref {
+        expr: Box<Expr>,
+    },
+    Member {
+        expr: Box<Expr>,
+        offset: usize,
+    },
+    Cast {
+        expr: Box<Expr>,
+    },
     PostfixIncrement {
         expr: Box<Expr>,
     },
@@ -150,12 +255,26 @@ pub enum ExprKind {
     Number {
         number: usize,
     },
+    /// A floating constant, already rounded to its type.
+    FloatNumber {
+        value: FloatValue,
+    },
     Func {
         name: FuncCallKind,
         args: Vec<Expr>,
     },
 }
 
+#[derive(PartialEq, Debug, Clone)]
+pub enum UnaryOpKind {
+    /// unary minus operator ('-')
+    Neg,
+    /// bitwise complement operator ('~')
+    BitNot,
+    /// logical negation operator ('!')
+    LogicNot,
+}
+
 #[derive(PartialEq, Debug, Clone)]
 pub enum BinaryOpKind {
     /// addition operator ('+')
@@ -227,6 +346,14 @@ impl BinaryOpKind {
             RowAssignOpKind::BitOrEqual => Ok(Self::BitOr),
         }
     }
+
+    /// Whether this yields an `int` truth value rather than a value of the operand type.
+    pub fn is_comparison(&self) -> bool {
+        matches!(
+            self,
+            Self::Lt | Self::LtEqual | Self::Equal | Self::NotEqual
+        )
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -0,0 +1,579 @@
+use std::cmp::Ordering;
+
+use super::types::FloatKind;
+
+/// A floating constant. Every operation is exact before it rounds once to the format of its
+/// type, so constants of each type, including the 64-bit mantissa of `long double`, are
+/// evaluated at their own precision.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FloatValue {
+    /// `mantissa * 2^exponent`. Zero keeps its sign.
+    Finite {
+        is_negative: bool,
+        mantissa: u64,
+        exponent: i32,
+    },
+    Infinity {
+        is_negative: bool,
+    },
+    NaN,
+}
+
+/// The precision, the exponent of the lowest mantissa bit of the subnormals and the largest
+/// biased exponent of the format of `kind`.
+fn format(kind: FloatKind) -> (u32, i32, i32) {
+    match kind {
+        FloatKind::Float => (24, -149, 254),
+        FloatKind::Double => (53, -1074, 2046),
+        FloatKind::LongDouble => (64, -16445, 32766),
+    }
+}
+
+impl FloatValue {
+    pub fn zero() -> Self {
+        Self::Finite {
+            is_negative: false,
+            mantissa: 0,
+            exponent: 0,
+        }
+    }
+
+    /// Parses the digits and the exponent of a floating constant without its suffix.
+    pub fn parse(literal: &str, kind: FloatKind) -> Self {
+        let lower = literal.to_ascii_lowercase();
+        let (radix, exponent_char, body) = match lower.strip_prefix("0x") {
+            Some(body) => (16, 'p', body),
+            None => (10, 'e', &lower[..]),
+        };
+        let (mantissa, exponent) = body.split_once(exponent_char).unwrap_or((body, "0"));
+        // Any exponent this large overflows or underflows anyway.
+        let exponent = exponent
+            .parse::<i32>()
+            .unwrap_or(if exponent.starts_with('-') {
+                -100_000
+            } else {
+                100_000
+            });
+        let mut digits = BigUint::default();
+        let mut fraction_len = 0;
+        let mut is_fraction = false;
+        for char in mantissa.chars() {
+            if char == '.' {
+                is_fraction = true;
+                continue;
+            }
+            digits.mul_add(radix, char.to_digit(radix).unwrap());
+            fraction_len += is_fraction as i32;
+        }
+
+        if radix == 16 {
+            return Self::from_big(digits, exponent - 4 * fraction_len, false, kind);
+        }
+        let exponent = exponent - fraction_len;
+        let len = digits.bit_len() as i32;
+        if len == 0 {
+            return Self::zero();
+        }
+        // Compare against the decimal range of `long double` with some margin.
+        if (len - 1) * 3 / 10 + exponent > 4940 {
+            return Self::Infinity { is_negative: false };
+        }
+        if len * 3 / 10 + 1 + exponent < -4970 {
+            return Self::zero();
+        }
+        if exponent >= 0 {
+            for _ in 0..exponent {
+                digits.mul_add(10, 0);
+            }
+            return Self::from_big(digits, 0, false, kind);
+        }
+        let mut divisor = BigUint::default();
+        divisor.mul_add(10, 1);
+        for _ in 0..-exponent {
+            divisor.mul_add(10, 0);
+        }
+        // Scale the dividend or the divisor so that the quotient has two bits more than any
+        // precision.
+        let scale = divisor.bit_len() as i32 - len + 66;
+        if scale >= 0 {
+            digits.shl(scale as usize);
+        } else {
+            divisor.shl(-scale as usize);
+        }
+        let (quotient, is_inexact) = digits.div(&divisor);
+        Self::round_parts(false, quotient, -scale, is_inexact, kind)
+    }
+
+    /// Converts an integer of the given signedness exactly.
+    pub fn from_integer(value: i64, is_signed: bool) -> Self {
+        let (is_negative, mantissa) = if is_signed {
+            (value < 0, value.unsigned_abs())
+        } else {
+            (false, value as u64)
+        };
+        Self::Finite {
+            is_negative,
+            mantissa,
+            exponent: 0,
+        }
+    }
+
+    /// Converts to the integer type of the given signedness, truncating toward zero and
+    /// saturating out of range values.
+    pub fn to_integer(self, is_signed: bool) -> i64 {
+        let (is_negative, magnitude) = match self {
+            Self::Finite {
+                is_negative,
+                mantissa,
+                exponent,
+            } => {
+                let magnitude = match exponent {
+                    0.. if mantissa != 0 && exponent > mantissa.leading_zeros() as i32 => u64::MAX,
+                    0.. => mantissa << exponent,
+                    -63..=-1 => mantissa >> -exponent,
+                    _ => 0,
+                };
+                (is_negative, magnitude)
+            }
+            Self::Infinity { is_negative } => (is_negative, u64::MAX),
+            Self::NaN => return 0,
+        };
+        match (is_signed, is_negative) {
+            (true, false) => magnitude.min(i64::MAX as u64) as i64,
+            (true, true) => (magnitude.min(1 << 63) as i64).wrapping_neg(),
+            (false, false) => magnitude as i64,
+            (false, true) => 0,
+        }
+    }
+
+    /// The bits of the value rounded to the format of `kind`, which is the x87 80-bit extended
+    /// format for `long double`.
+    pub fn to_bits(self, kind: FloatKind) -> u128 {
+        let (precision, min_exponent, max_exponent) = format(kind);
+        // The extended format stores the integer bit of the mantissa.
+        let (sign_shift, exponent_shift, integer_bit) = match kind {
+            FloatKind::Float => (31, 23, 0),
+            FloatKind::Double => (63, 52, 0),
+            FloatKind::LongDouble => (79, 64, 1 << 63),
+        };
+        let fraction_mask = (1 << (precision - 1)) - 1;
+        let (is_negative, exponent, mantissa) = match self.round(kind) {
+            Self::Finite {
+                is_negative,
+                mantissa,
+                ..
+            } if mantissa >> (precision - 1) == 0 => (is_negative, 0, mantissa),
+            Self::Finite {
+                is_negative,
+                mantissa,
+                exponent,
+            } => (
+                is_negative,
+                exponent - min_exponent + 1,
+                mantissa & fraction_mask | integer_bit,
+            ),
+            Self::Infinity { is_negative } => (is_negative, max_exponent + 1, integer_bit),
+            Self::NaN => (false, max_exponent + 1, integer_bit | 1 << (precision - 2)),
+        };
+        (is_negative as u128) << sign_shift
+            | (exponent as u128) << exponent_shift
+            | mantissa as u128
+    }
+
+    /// Rounds to the nearest value of the format of `kind`, ties to even.
+    pub fn round(self, kind: FloatKind) -> Self {
+        match self {
+            Self::Finite {
+                is_negative,
+                mantissa,
+                exponent,
+            } => Self::round_parts(is_negative, mantissa as u128, exponent, false, kind),
+            _ => self,
+        }
+    }
+
+    pub fn neg(self) -> Self {
+        match self {
+            Self::Finite {
+                is_negative,
+                mantissa,
+                exponent,
+            } => Self::Finite {
+                is_negative: !is_negative,
+                mantissa,
+                exponent,
+            },
+            Self::Infinity { is_negative } => Self::Infinity {
+                is_negative: !is_negative,
+            },
+            Self::NaN => Self::NaN,
+        }
+    }
+
+    pub fn add(self, rhs: Self, kind: FloatKind) -> Self {
+        let (
+            Self::Finite {
+                is_negative: lhs_negative,
+                mantissa: lhs_mantissa,
+                exponent: lhs_exponent,
+            },
+            Self::Finite {
+                is_negative: rhs_negative,
+                mantissa: rhs_mantissa,
+                exponent: rhs_exponent,
+            },
+        ) = (self, rhs)
+        else {
+            return match (self, rhs) {
+                (Self::Infinity { is_negative: lhs }, Self::Infinity { is_negative: rhs })
+                    if lhs != rhs =>
+                {
+                    Self::NaN
+                }
+                (Self::NaN, _) | (_, Self::NaN) => Self::NaN,
+                (Self::Infinity { .. }, _) => self,
+                _ => rhs,
+            };
+        };
+        match (lhs_mantissa, rhs_mantissa) {
+            (0, 0) => {
+                return Self::Finite {
+                    is_negative: lhs_negative && rhs_negative,
+                    mantissa: 0,
+                    exponent: 0,
+                }
+            }
+            (0, _) => return rhs.round(kind),
+            (_, 0) => return self.round(kind),
+            _ => {}
+        }
+
+        // Place the operand with the higher leading bit at bit 125, which leaves room for a
+        // carry and bits enough below the precision to round the sum after it is exact.
+        let top = |mantissa: u64, exponent: i32| exponent - mantissa.leading_zeros() as i32;
+        let (big, small) = if top(lhs_mantissa, lhs_exponent) >= top(rhs_mantissa, rhs_exponent) {
+            (
+                (lhs_negative, lhs_mantissa, lhs_exponent),
+                (rhs_negative, rhs_mantissa, rhs_exponent),
+            )
+        } else {
+            (
+                (rhs_negative, rhs_mantissa, rhs_exponent),
+                (lhs_negative, lhs_mantissa, lhs_exponent),
+            )
+        };
+        let shift = 62 + big.1.leading_zeros() as i32;
+        let exponent = big.2 - shift;
+        let big_mantissa = (big.1 as u128) << shift;
+        let small_mantissa = match small.2 - exponent {
+            shift @ 0.. => (small.1 as u128) << shift,
+            // Bits shifted out only need to be seen by the rounding, as the lowest bit.
+            shift @ -127..=-1 => {
+                let shift = -shift;
+                (small.1 as u128) >> shift | ((small.1 as u128) & ((1 << shift) - 1) != 0) as u128
+            }
+            _ => 1,
+        };
+        if big.0 == small.0 {
+            return Self::round_parts(big.0, big_mantissa + small_mantissa, exponent, false, kind);
+        }
+        match big_mantissa.cmp(&small_mantissa) {
+            Ordering::Greater => {
+                Self::round_parts(big.0, big_mantissa - small_mantissa, exponent, false, kind)
+            }
+            Ordering::Less => Self::round_parts(
+                small.0,
+                small_mantissa - big_mantissa,
+                exponent,
+                false,
+                kind,
+            ),
+            Ordering::Equal => Self::zero(),
+        }
+    }
+
+    pub fn sub(self, rhs: Self, kind: FloatKind) -> Self {
+        self.add(rhs.neg(), kind)
+    }
+
+    pub fn mul(self, rhs: Self, kind: FloatKind) -> Self {
+        match (self, rhs) {
+            (
+                Self::Finite {
+                    is_negative: lhs_negative,
+                    mantissa: lhs_mantissa,
+                    exponent: lhs_exponent,
+                },
+                Self::Finite {
+                    is_negative: rhs_negative,
+                    mantissa: rhs_mantissa,
+                    exponent: rhs_exponent,
+                },
+            ) => Self::round_parts(
+                lhs_negative != rhs_negative,
+                lhs_mantissa as u128 * rhs_mantissa as u128,
+                lhs_exponent + rhs_exponent,
+                false,
+                kind,
+            ),
+            (Self::NaN, _) | (_, Self::NaN) => Self::NaN,
+            _ if self.is_zero() || rhs.is_zero() => Self::NaN,
+            _ => Self::Infinity {
+                is_negative: self.is_negative() != rhs.is_negative(),
+            },
+        }
+    }
+
+    pub fn div(self, rhs: Self, kind: FloatKind) -> Self {
+        let is_negative = self.is_negative() != rhs.is_negative();
+        match (self, rhs) {
+            (Self::NaN, _) | (_, Self::NaN) => Self::NaN,
+            (Self::Infinity { .. }, Self::Infinity { .. }) => Self::NaN,
+            (Self::Infinity { .. }, _) => Self::Infinity { is_negative },
+            _ if self.is_zero() && rhs.is_zero() => Self::NaN,
+            (_, Self::Infinity { .. }) => Self::Finite {
+                is_negative,
+                mantissa: 0,
+                exponent: 0,
+            },
+            _ if rhs.is_zero() => Self::Infinity { is_negative },
+            (
+                Self::Finite {
+                    mantissa: lhs_mantissa,
+                    exponent: lhs_exponent,
+                    ..
+                },
+                Self::Finite {
+                    mantissa: rhs_mantissa,
+                    exponent: rhs_exponent,
+                    ..
+                },
+            ) => {
+                if lhs_mantissa == 0 {
+                    return Self::Finite {
+                        is_negative,
+                        mantissa: 0,
+                        exponent: 0,
+                    };
+                }
+                // With the dividend at bit 127 and the divisor at bit 63, the quotient has at
+                // least 64 bits, and one more is divided out of the remainder.
+                let lhs_shift = lhs_mantissa.leading_zeros() as i32 + 64;
+                let rhs_shift = rhs_mantissa.leading_zeros() as i32;
+                let dividend = (lhs_mantissa as u128) << lhs_shift;
+                let divisor = (rhs_mantissa as u128) << rhs_shift;
+                let remainder = (dividend % divisor) << 1;
+                let is_odd = remainder >= divisor;
+                Self::round_parts(
+                    is_negative,
+                    (dividend / divisor) << 1 | is_odd as u128,
+                    lhs_exponent - lhs_shift - rhs_exponent + rhs_shift - 1,
+                    remainder != if is_odd { divisor } else { 0 },
+                    kind,
+                )
+            }
+        }
+    }
+
+    pub fn partial_cmp(self, rhs: Self) -> Option<Ordering> {
+        if let (Self::NaN, _) | (_, Self::NaN) = (self, rhs) {
+            return None;
+        }
+        if self.is_zero() && rhs.is_zero() {
+            return Some(Ordering::Equal);
+        }
+        if self.is_negative() != rhs.is_negative() {
+            return Some(if self.is_negative() {
+                Ordering::Less
+            } else {
+                Ordering::Greater
+            });
+        }
+        // Compare the magnitudes by their leading bit, then by their normalized mantissas.
+        let magnitude = |value: Self| match value {
+            Self::Finite { mantissa: 0, .. } => (i32::MIN, 0),
+            Self::Finite {
+                mantissa, exponent, ..
+            } => {
+                let shift = mantissa.leading_zeros();
+                (exponent - shift as i32, mantissa << shift)
+            }
+            _ => (i32::MAX, 0),
+        };
+        let ordering = magnitude(self).cmp(&magnitude(rhs));
+        Some(if self.is_negative() {
+            ordering.reverse()
+        } else {
+            ordering
+        })
+    }
+
+    pub fn is_zero(self) -> bool {
+        matches!(self, Self::Finite { mantissa: 0, .. })
+    }
+
+    fn is_negative(self) -> bool {
+        match self {
+            Self::Finite { is_negative, .. } | Self::Infinity { is_negative } => is_negative,
+            Self::NaN => false,
+        }
+    }
+
+    /// Rounds a big integer times `2^exponent`.
+    fn from_big(value: BigUint, exponent: i32, is_inexact: bool, kind: FloatKind) -> Self {
+        let shift = value.bit_len().saturating_sub(128);
+        let (mantissa, is_shifted_inexact) = value.top_bits(shift);
+        Self::round_parts(
+            false,
+            mantissa,
+            exponent + shift as i32,
+            is_inexact || is_shifted_inexact,
+            kind,
+        )
+    }
+
+    /// Rounds `mantissa * 2^exponent` to the format of `kind`, ties to even. `is_inexact` means
+    /// that nonzero bits follow the mantissa, which then has at least one bit more than the
+    /// precision.
+    fn round_parts(
+        is_negative: bool,
+        mantissa: u128,
+        exponent: i32,
+        is_inexact: bool,
+        kind: FloatKind,
+    ) -> Self {
+        let (precision, min_exponent, max_exponent) = format(kind);
+        let len = 128 - mantissa.leading_zeros() as i32;
+        let mut result_exponent = (exponent + len - precision as i32).max(min_exponent);
+        let shift = result_exponent - exponent;
+        let mut result = if shift <= 0 {
+            mantissa << -shift
+        } else {
+            let (result, half, rest) = match shift {
+                1..=127 => (
+                    mantissa >> shift,
+                    mantissa >> (shift - 1) & 1 == 1,
+                    mantissa & ((1 << (shift - 1)) - 1) != 0,
+                ),
+                128 => (0, mantissa >> 127 == 1, mantissa << 1 != 0),
+                _ => (0, false, mantissa != 0),
+            };
+            result + (half && (rest || is_inexact || result & 1 == 1)) as u128
+        };
+        if result >> precision == 1 {
+            result >>= 1;
+            result_exponent += 1;
+        }
+        if result >> (precision - 1) == 1 && result_exponent - min_exponent + 1 > max_exponent {
+            return Self::Infinity { is_negative };
+        }
+        Self::Finite {
+            is_negative,
+            mantissa: result as u64,
+            exponent: result_exponent,
+        }
+    }
+}
+
+/// An unsigned integer of any size, with just what parsing decimal constants needs.
+#[derive(Debug, Clone, Default)]
+struct BigUint {
+    /// The 32-bit digits, least significant first.
+    digits: Vec<u32>,
+}
+
+impl BigUint {
+    /// Sets the value to `self * mul + add`.
+    fn mul_add(&mut self, mul: u32, add: u32) {
+        let mut carry = add as u64;
+        for digit in &mut self.digits {
+            let value = *digit as u64 * mul as u64 + carry;
+            *digit = value as u32;
+            carry = value >> 32;
+        }
+        if carry != 0 {
+            self.digits.push(carry as u32);
+        }
+    }
+
+    fn shl(&mut self, shift: usize) {
+        let (words, bits) = (shift / 32, shift % 32);
+        let mut digits = vec![0; words];
+        let mut carry = 0;
+        for &digit in &self.digits {
+            let value = (digit as u64) << bits;
+            digits.push(value as u32 | carry);
+            carry = (value >> 32) as u32;
+        }
+        if carry != 0 {
+            digits.push(carry);
+        }
+        self.digits = digits;
+    }
+
+    fn bit_len(&self) -> usize {
+        match self.digits.iter().rposition(|&digit| digit != 0) {
+            Some(index) => index * 32 + 32 - self.digits[index].leading_zeros() as usize,
+            None => 0,
+        }
+    }
+
+    fn bit(&self, index: usize) -> bool {
+        self.digits[index / 32] >> (index % 32) & 1 == 1
+    }
+
+    /// The bits above the lowest `shift` ones, which must fit in 128 bits, and whether any of
+    /// the lowest ones is set.
+    fn top_bits(&self, shift: usize) -> (u128, bool) {
+        let mut value = 0;
+        let mut is_inexact = false;
+        for index in (0..self.bit_len()).rev() {
+            if index >= shift {
+                value = value << 1 | self.bit(index) as u128;
+            } else {
+                is_inexact |= self.bit(index);
+            }
+        }
+        (value, is_inexact)
+    }
+
+    /// Divides by `divisor`, giving a quotient that must fit in 128 bits and whether there is a
+    /// remainder.
+    fn div(&self, divisor: &Self) -> (u128, bool) {
+        let mut quotient = 0;
+        let mut remainder = Self::default();
+        for index in (0..self.bit_len()).rev() {
+            remainder.shl(1);
+            if self.bit(index) {
+                remainder.mul_add(1, 1);
+            }
+            quotient <<= 1;
+            if remainder.cmp(divisor) != Ordering::Less {
+                remainder.sub(divisor);
+                quotient |= 1;
+            }
+        }
+        (quotient, remainder.bit_len() != 0)
+    }
+
+    fn cmp(&self, other: &Self) -> Ordering {
+        let len = self.digits.len().max(other.digits.len());
+        let digit = |value: &Self, index: usize| value.digits.get(index).copied().unwrap_or(0);
+        (0..len)
+            .rev()
+            .map(|index| digit(self, index).cmp(&digit(other, index)))
+            .find(|ordering| *ordering != Ordering::Equal)
+            .unwrap_or(Ordering::Equal)
+    }
+
+    /// Subtracts `other`, which must not be greater.
+    fn sub(&mut self, other: &Self) {
+        let mut borrow = 0;
+        for (index, digit) in self.digits.iter_mut().enumerate() {
+            let value =
+                *digit as i64 - other.digits.get(index).copied().unwrap_or(0) as i64 - borrow;
+            *digit = value as u32;
+            borrow = (value < 0) as i64;
+        }
+    }
+}
@@ -0,0 +1,28 @@
+use super::{stmt::Stmt, variable::Variable};
+
+#[derive(Debug)]
+pub struct Function {
+    pub name: String,
+    pub params: Vec<Variable>,
+    pub body: Stmt,
+    pub offset: usize,
+    pub is_static: bool,
+}
+
+impl Function {
+    pub fn new(
+        name: String,
+        params: Vec<Variable>,
+        body: Stmt,
+        offset: usize,
+        is_static: bool,
+    ) -> Self {
+        Self {
+            name,
+            params,
+            body,
+            offset,
+            is_static,
+        }
+    }
+}
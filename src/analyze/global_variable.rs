@@ -0,0 +1,74 @@
+use super::types::Type;
+
+#[derive(Debug)]
+pub struct GlobalVariable {
+    pub name: String,
+    pub ty: Type,
+    pub init: Option<GlobalInit>,
+    pub is_const: bool,
+    /// Has internal linkage and gets no `.globl` directive.
+    pub is_static: bool,
+    /// Only declared here and defined elsewhere.
+    pub is_extern: bool,
+}
+
+impl GlobalVariable {
+    pub fn new(
+        name: String,
+        ty: Type,
+        init: Option<GlobalInit>,
+        is_const: bool,
+        is_static: bool,
+        is_extern: bool,
+    ) -> Self {
+        Self {
+            name,
+            ty,
+            init,
+            is_const,
+            is_static,
+            is_extern,
+        }
+    }
+
+    /// Whether the variable can be placed in `.bss`.
+    pub fn is_zero(&self) -> bool {
+        self.init.as_ref().is_none_or(|init| {
+            init.relocations.is_empty() && init.bytes.iter().all(|byte| *byte == 0)
+        })
+    }
+}
+
+/// The initial image of a global variable.
+#[derive(Debug, Clone)]
+pub struct GlobalInit {
+    pub bytes: Vec<u8>,
+    pub relocations: Vec<Relocation>,
+}
+
+impl GlobalInit {
+    pub fn new(size: usize) -> Self {
+        Self {
+            bytes: vec![0; size],
+            relocations: Vec::new(),
+        }
+    }
+}
+
+/// An 8-byte slot holding the address `label + addend`.
+#[derive(Debug, Clone)]
+pub struct Relocation {
+    pub offset: usize,
+    pub label: String,
+    pub addend: i64,
+}
+
+impl Relocation {
+    pub fn new(offset: usize, label: String, addend: i64) -> Self {
+        Self {
+            offset,
+            label,
+            addend,
+        }
+    }
+}
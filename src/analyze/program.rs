@@ -1,15 +1,15 @@
 use std::collections::VecDeque;
 
-use super::stmt::Stmt;
+use super::{function::Function, global_variable::GlobalVariable};
 
 #[derive(Debug)]
 pub struct Program {
-    pub stmts: VecDeque<Stmt>,
-    pub offset: usize,
+    pub functions: VecDeque<Function>,
+    pub globals: Vec<GlobalVariable>,
 }
 
 impl Program {
-    pub fn new(stmts: VecDeque<Stmt>, offset: usize) -> Self {
-        Self { stmts, offset }
+    pub fn new(functions: VecDeque<Function>, globals: Vec<GlobalVariable>) -> Self {
+        Self { functions, globals }
     }
 }
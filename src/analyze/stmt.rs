@@ -1,10 +1,11 @@
 use crate::file::position::Position;
 
-use super::expr::Expr;
+use super::{expr::Expr, variable::Variable};
 
 #[derive(Debug, Clone)]
 pub struct Stmt {
     pub kind: StmtKind,
+    #[allow(dead_code)]
     pub position: Position,
 }
 
@@ -48,9 +49,9 @@ impl Stmt {
     ) -> Self {
         Self {
             kind: StmtKind::For {
-                init_expr,
+                init_expr: Box::new(init_expr),
                 condition_expr,
-                delta_expr,
+                delta_expr: Box::new(delta_expr),
                 run_stmt: Box::new(run_stmt),
             },
             position,
@@ -67,6 +68,91 @@ impl Stmt {
         }
     }
 
+    pub fn new_do_while(run_stmt: Stmt, condition_expr: Expr, position: Position) -> Self {
+        Self {
+            kind: StmtKind::DoWhile {
+                run_stmt: Box::new(run_stmt),
+                condition_expr,
+            },
+            position,
+        }
+    }
+
+    pub fn new_zero_fill(var: Variable, position: Position) -> Self {
+        Self {
+            kind: StmtKind::ZeroFill { var },
+            position,
+        }
+    }
+
+    pub fn new_break(position: Position) -> Self {
+        Self {
+            kind: StmtKind::Break,
+            position,
+        }
+    }
+
+    pub fn new_continue(position: Position) -> Self {
+        Self {
+            kind: StmtKind::Continue,
+            position,
+        }
+    }
+
+    pub fn new_switch(
+        condition_expr: Expr,
+        run_stmt: Stmt,
+        cases: Vec<i64>,
+        has_default: bool,
+        position: Position,
+    ) -> Self {
+        Self {
+            kind: StmtKind::Switch {
+                condition_expr,
+                run_stmt: Box::new(run_stmt),
+                cases,
+                has_default,
+            },
+            position,
+        }
+    }
+
+    pub fn new_case(index: usize, stmt: Stmt, position: Position) -> Self {
+        Self {
+            kind: StmtKind::Case {
+                index,
+                stmt: Box::new(stmt),
+            },
+            position,
+        }
+    }
+
+    pub fn new_default(stmt: Stmt, position: Position) -> Self {
+        Self {
+            kind: StmtKind::Default {
+                stmt: Box::new(stmt),
+            },
+            position,
+        }
+    }
+
+    pub fn new_goto(label: String, position: Position) -> Self {
+        Self {
+            kind: StmtKind::Goto { label },
+            position,
+        }
+    }
+
+    pub fn new_label(label: String, stmt: Stmt, position: Position) -> Self {
+        Self {
+            kind: StmtKind::Label {
+                label,
+                stmt: Box::new(stmt),
+            },
+            position,
+        }
+    }
+
     pub fn new_cpd(stmts: Vec<Stmt>, position: Position) -> Self {
         Self {
             kind: StmtKind::Cpd { stmts },
@@ -89,15 +175,46 @@ pub enum StmtKind {
         else_stmt: Box<Option<Stmt>>,
     },
     For {
-        init_expr: Option<Expr>,
+        init_expr: Box<Option<Expr>>,
         condition_expr: Expr,
-        delta_expr: Option<Expr>,
+        delta_expr: Box<Option<Expr>>,
         run_stmt: Box<Stmt>,
     },
     While {
         condition_expr: Expr,
         run_stmt: Box<Stmt>,
     },
+    DoWhile {
+        run_stmt: Box<Stmt>,
+        condition_expr: Expr,
+    },
+    Break,
+    Continue,
+    /// Clears the storage of `var` before its initializer list is stored.
+    ZeroFill {
+        var: Variable,
+    },
+    /// `cases` holds the value of each case label, indexed by `Case::index`.
+    Switch {
+        condition_expr: Expr,
+        run_stmt: Box<Stmt>,
+        cases: Vec<i64>,
+        has_default: bool,
+    },
+    Case {
+        index: usize,
+        stmt: Box<Stmt>,
+    },
+    Default {
+        stmt: Box<Stmt>,
+    },
+    Goto {
+        label: String,
+    },
+    Label {
+        label: String,
+        stmt: Box<Stmt>,
+    },
     Cpd {
         stmts: Vec<Stmt>,
     },
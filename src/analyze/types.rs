@@ -0,0 +1,356 @@
+use std::{cell::RefCell, fmt, rc::Rc};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Type {
+    Void,
+    Integer {
+        kind: IntegerKind,
+        signed: bool,
+    },
+    Float {
+        kind: FloatKind,
+    },
+    Pointer {
+        ty: Box<Type>,
+    },
+    Array {
+        ty: Box<Type>,
+        len: usize,
+    },
+    Function {
+        ret: Box<Type>,
+        params: Vec<Type>,
+        variadic: bool,
+    },
+    Struct {
+        struct_type: Rc<StructType>,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum IntegerKind {
+    Char,
+    Short,
+    Int,
+    Long,
+    LongLong,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum FloatKind {
+    Float,
+    Double,
+    LongDouble,
+}
+
+impl Type {
+    pub fn new_integer(kind: IntegerKind, signed: bool) -> Self {
+        Self::Integer { kind, signed }
+    }
+
+    pub fn new_int() -> Self {
+        Self::new_integer(IntegerKind::Int, true)
+    }
+
+    pub fn new_long() -> Self {
+        Self::new_integer(IntegerKind::Long, true)
+    }
+
+    pub fn new_unsigned_long() -> Self {
+        Self::new_integer(IntegerKind::Long, false)
+    }
+
+    pub fn new_float(kind: FloatKind) -> Self {
+        Self::Float { kind }
+    }
+
+    pub fn new_double() -> Self {
+        Self::new_float(FloatKind::Double)
+    }
+
+    pub fn new_pointer(ty: Type) -> Self {
+        Self::Pointer { ty: Box::new(ty) }
+    }
+
+    pub fn new_array(ty: Type, len: usize) -> Self {
+        Self::Array {
+            ty: Box::new(ty),
+            len,
+        }
+    }
+
+    pub fn new_function(ret: Type, params: Vec<Type>, variadic: bool) -> Self {
+        Self::Function {
+            ret: Box::new(ret),
+            params,
+            variadic,
+        }
+    }
+
+    pub fn size(&self) -> usize {
+        match self {
+            Self::Void => 1,
+            Self::Integer { kind, .. } => match kind {
+                IntegerKind::Char => 1,
+                IntegerKind::Short => 2,
+                IntegerKind::Int => 4,
+                IntegerKind::Long | IntegerKind::LongLong => 8,
+            },
+            Self::Float { kind } => match kind {
+                FloatKind::Float => 4,
+                FloatKind::Double => 8,
+                FloatKind::LongDouble => 16,
+            },
+            Self::Pointer { .. } => 8,
+            Self::Array { ty, len } => ty.size() * len,
+            Self::Function { .. } => 1,
+            Self::Struct { struct_type } => struct_type.size(),
+        }
+    }
+
+    pub fn align(&self) -> usize {
+        match self {
+            Self::Array { ty, .. } => ty.align(),
+            Self::Struct { struct_type } => struct_type.align(),
+            _ => self.size(),
+        }
+    }
+
+    pub fn is_integer(&self) -> bool {
+        matches!(self, Self::Integer { .. })
+    }
+
+    pub fn is_float(&self) -> bool {
+        matches!(self, Self::Float { .. })
+    }
+
+    /// Whether this is `long double`, which is computed on the x87 FPU rather than with SSE.
+    pub fn is_long_double(&self) -> bool {
+        matches!(
+            self,
+            Self::Float {
+                kind: FloatKind::LongDouble
+            }
+        )
+    }
+
+    pub fn is_arithmetic(&self) -> bool {
+        self.is_integer() || self.is_float()
+    }
+
+    pub fn is_pointer(&self) -> bool {
+        matches!(self, Self::Pointer { .. })
+    }
+
+    pub fn is_scalar(&self) -> bool {
+        self.is_arithmetic() || self.is_pointer()
+    }
+
+    pub fn is_array(&self) -> bool {
+        matches!(self, Self::Array { .. })
+    }
+
+    pub fn is_struct(&self) -> bool {
+        matches!(self, Self::Struct { .. })
+    }
+
+    /// Returns the type pointed to, if this is a pointer type.
+    pub fn pointee(&self) -> Option<&Type> {
+        match self {
+            Self::Pointer { ty } => Some(ty),
+            _ => None,
+        }
+    }
+
+    /// Converts array types to pointers to their first element.
+    pub fn decay(&self) -> Self {
+        match self {
+            Self::Array { ty, .. } => Self::new_pointer(*ty.clone()),
+            ty => ty.clone(),
+        }
+    }
+
+    pub fn is_unprototyped(&self) -> bool {
+        matches!(self, Self::Function { params, variadic: true, .. } if params.is_empty())
+    }
+
+    pub fn is_compatible(&self, other: &Type) -> bool {
+        match (self, other) {
+            (Self::Function { ret: lhs_ret, .. }, Self::Function { ret: rhs_ret, .. })
+                if self.is_unprototyped() || other.is_unprototyped() =>
+            {
+                lhs_ret.is_compatible(rhs_ret)
+            }
+            _ => self == other,
+        }
+    }
+
+    pub fn is_signed(&self) -> bool {
+        matches!(self, Self::Integer { signed: true, .. })
+    }
+
+    /// Applies the integer promotions.
+    pub fn promote(&self) -> Self {
+        match self {
+            Self::Integer { kind, .. } if *kind < IntegerKind::Int => Self::new_int(),
+            ty => ty.clone(),
+        }
+    }
+
+    /// Computes the common type of the usual arithmetic conversions.
+    pub fn common(lhs: &Type, rhs: &Type) -> Self {
+        let (lhs, rhs) = (lhs.promote(), rhs.promote());
+        match (&lhs, &rhs) {
+            (Self::Float { kind: lhs_kind }, Self::Float { kind: rhs_kind }) => {
+                Self::new_float(*lhs_kind.max(rhs_kind))
+            }
+            (Self::Float { .. }, _) => lhs,
+            (_, Self::Float { .. }) => rhs,
+            (
+                Self::Integer {
+                    kind: lhs_kind,
+                    signed: lhs_signed,
+                },
+                Self::Integer {
+                    kind: rhs_kind,
+                    signed: rhs_signed,
+                },
+            ) => {
+                if lhs_signed == rhs_signed {
+                    return Self::new_integer(*lhs_kind.max(rhs_kind), *lhs_signed);
+                }
+                let (signed_kind, unsigned_kind) = if *lhs_signed {
+                    (*lhs_kind, *rhs_kind)
+                } else {
+                    (*rhs_kind, *lhs_kind)
+                };
+                let signed_ty = Self::new_integer(signed_kind, true);
+                let unsigned_ty = Self::new_integer(unsigned_kind, false);
+                if signed_kind <= unsigned_kind {
+                    unsigned_ty
+                } else if signed_ty.size() > unsigned_ty.size() {
+                    signed_ty
+                } else {
+                    Self::new_integer(signed_kind, false)
+                }
+            }
+            _ => lhs,
+        }
+    }
+}
+
+/// A struct or union type. Two struct types are the same type only if they come from the same
+/// declaration, so equality compares identity rather than members.
+pub struct StructType {
+    pub tag: Option<String>,
+    pub is_union: bool,
+    body: RefCell<Option<StructBody>>,
+}
+
+struct StructBody {
+    members: Vec<Member>,
+    size: usize,
+    align: usize,
+}
+
+#[derive(Debug, Clone)]
+pub struct Member {
+    pub name: Option<String>,
+    pub ty: Type,
+    pub offset: usize,
+}
+
+impl StructType {
+    pub fn new(tag: Option<String>, is_union: bool) -> Self {
+        Self {
+            tag,
+            is_union,
+            body: RefCell::new(None),
+        }
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.body.borrow().is_some()
+    }
+
+    /// Completes the type, laying out the members as the System V ABI does.
+    pub fn define(&self, members: Vec<(Option<String>, Type)>) {
+        let mut size = 0;
+        let mut align = 1;
+        let members = members
+            .into_iter()
+            .map(|(name, ty)| {
+                align = align.max(ty.align());
+                let offset = if self.is_union {
+                    size = size.max(ty.size());
+                    0
+                } else {
+                    let offset = align_to(size, ty.align());
+                    size = offset + ty.size();
+                    offset
+                };
+                Member { name, ty, offset }
+            })
+            .collect();
+        *self.body.borrow_mut() = Some(StructBody {
+            members,
+            size: align_to(size, align),
+            align,
+        });
+    }
+
+    pub fn size(&self) -> usize {
+        self.body.borrow().as_ref().map_or(0, |body| body.size)
+    }
+
+    pub fn align(&self) -> usize {
+        self.body.borrow().as_ref().map_or(1, |body| body.align)
+    }
+
+    pub fn members(&self) -> Vec<Member> {
+        self.body
+            .borrow()
+            .as_ref()
+            .map_or_else(Vec::new, |body| body.members.clone())
+    }
+
+    /// Looks up a member by name, searching anonymous members recursively.
+    pub fn find_member(&self, name: &str) -> Option<Member> {
+        let body = self.body.borrow();
+        for member in body.as_ref()?.members.iter() {
+            match (&member.name, &member.ty) {
+                (Some(member_name), _) if member_name == name => return Some(member.clone()),
+                (None, Type::Struct { struct_type }) => {
+                    if let Some(inner) = struct_type.find_member(name) {
+                        return Some(Member {
+                            offset: member.offset + inner.offset,
+                            ..inner
+                        });
+                    }
+                }
+                _ => {}
+            }
+        }
+        None
+    }
+}
+
+impl PartialEq for StructType {
+    fn eq(&self, other: &Self) -> bool {
+        std::ptr::eq(self, other)
+    }
+}
+
+impl fmt::Debug for StructType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let keyword = if self.is_union { "union" } else { "struct" };
+        match &self.tag {
+            Some(tag) => write!(f, "{} {}", keyword, tag),
+            None => write!(f, "{} <anonymous>", keyword),
+        }
+    }
+}
+
+pub fn align_to(n: usize, align: usize) -> usize {
+    n.div_ceil(align) * align
+}
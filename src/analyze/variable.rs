@@ -1,10 +1,39 @@
+use super::types::Type;
+
 #[derive(Debug, Clone)]
 pub struct Variable {
-    pub offset: usize,
+    pub kind: VariableKind,
+    pub ty: Type,
 }
 
 impl Variable {
-    pub fn new(offset: usize) -> Self {
-        Self { offset }
+    pub fn new_local(offset: usize, ty: Type) -> Self {
+        Self {
+            kind: VariableKind::Local { offset },
+            ty,
+        }
+    }
+
+    pub fn new_global(name: String, ty: Type) -> Self {
+        Self {
+            kind: VariableKind::Global { name },
+            ty,
+        }
+    }
+
+    /// The label of a variable with static storage.
+    pub fn label(&self) -> Option<&str> {
+        match &self.kind {
+            VariableKind::Local { .. } => None,
+            VariableKind::Global { name } => Some(name),
+        }
     }
 }
+
+#[derive(Debug, Clone)]
+pub enum VariableKind {
+    /// Addressed relative to `rbp`.
+    Local { offset: usize },
+    /// Addressed relative to `rip` by its label.
+    Global { name: String },
+}
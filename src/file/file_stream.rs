@@ -59,6 +59,15 @@ impl FileStream {
         matches!(self.peek(), Some((_, '0'..='9')))
     }
 
+    /// Whether a numeric constant starts here, including a floating one like `.5`.
+    pub fn starts_with_numeric_constant(&self) -> bool {
+        match self.chars.front() {
+            Some((_, '0'..='9')) => true,
+            Some((_, '.')) => matches!(self.chars.get(1), Some((_, '0'..='9'))),
+            _ => false,
+        }
+    }
+
     pub fn starts_with_alphabet(&self) -> bool {
         matches!(self.peek(), Some((_, 'a'..='z' | 'A'..='Z')))
     }
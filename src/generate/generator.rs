@@ -1,53 +1,44 @@
 mod expr;
+mod function;
+mod global;
 mod stmt;
 
 use std::{fs::File, io::BufWriter, io::Write};
 
-use crate::analyze::program::Program;
+use crate::analyze::{program::Program, types::Type};
 
 use super::register::Register;
 
 pub struct Generator {
     label: usize,
     stack: usize,
+    function: String,
+    break_labels: Vec<String>,
+    continue_labels: Vec<String>,
+    switch_labels: Vec<usize>,
 }
 
 impl Generator {
     pub fn new() -> Self {
-        Self { label: 0, stack: 0 }
+        Self {
+            label: 0,
+            stack: 0,
+            function: String::new(),
+            break_labels: Vec::new(),
+            continue_labels: Vec::new(),
+            switch_labels: Vec::new(),
+        }
     }
 
     pub fn generate(&mut self, f: &mut BufWriter<File>, program: Program) -> anyhow::Result<()> {
         writeln!(f, ".intel_syntax noprefix")?;
-        writeln!(f, ".globl main")?;
-        writeln!(f, "main:")?;
-        self.generate_push_with_reg(f, Register::Rbp)?;
-        writeln!(
-            f,
-            "\tmov {}, {}",
-            Register::Rbp.qword(),
-            Register::Rsp.qword()
-        )?;
-        let offset = if program.offset % 16 == 0 {
-            program.offset
-        } else {
-            program.offset + program.offset % 16
-        };
-        writeln!(f, "\tsub {}, {}", Register::Rsp.qword(), offset)?;
-
-        for stmt in program.stmts.into_iter() {
-            self.generate_stmt(f, stmt)?;
+        for global in program.globals.into_iter() {
+            self.generate_global(f, global)?;
+        }
+        writeln!(f, ".text")?;
+        for function in program.functions.into_iter() {
+            self.generate_function(f, function)?;
         }
-
-        writeln!(f, ".Lmain_ret:")?;
-        writeln!(
-            f,
-            "\tmov {}, {}",
-            Register::Rsp.qword(),
-            Register::Rbp.qword()
-        )?;
-        self.generate_pop(f, Register::Rbp)?;
-        writeln!(f, "\tret")?;
         Ok(())
     }
 
@@ -73,9 +64,365 @@ impl Generator {
         Ok(())
     }
 
+    fn generate_load(
+        &mut self,
+        f: &mut BufWriter<File>,
+        ty: &Type,
+        dst: Register,
+        addr: &str,
+    ) -> anyhow::Result<()> {
+        match (ty.size(), ty.is_signed()) {
+            (1, true) => writeln!(f, "\tmovsx {}, byte ptr [{}]", dst.qword(), addr)?,
+            (1, false) => writeln!(f, "\tmovzx {}, byte ptr [{}]", dst.dword(), addr)?,
+            (2, true) => writeln!(f, "\tmovsx {}, word ptr [{}]", dst.qword(), addr)?,
+            (2, false) => writeln!(f, "\tmovzx {}, word ptr [{}]", dst.dword(), addr)?,
+            (4, true) => writeln!(f, "\tmovsxd {}, dword ptr [{}]", dst.qword(), addr)?,
+            (4, false) => writeln!(f, "\tmov {}, dword ptr [{}]", dst.dword(), addr)?,
+            _ => writeln!(f, "\tmov {}, qword ptr [{}]", dst.qword(), addr)?,
+        }
+        Ok(())
+    }
+
+    fn generate_store(
+        &mut self,
+        f: &mut BufWriter<File>,
+        ty: &Type,
+        addr: &str,
+        src: Register,
+    ) -> anyhow::Result<()> {
+        match ty.size() {
+            1 => writeln!(f, "\tmov byte ptr [{}], {}", addr, src.byte())?,
+            2 => writeln!(f, "\tmov word ptr [{}], {}", addr, src.word())?,
+            4 => writeln!(f, "\tmov dword ptr [{}], {}", addr, src.dword())?,
+            _ => writeln!(f, "\tmov qword ptr [{}], {}", addr, src.qword())?,
+        }
+        Ok(())
+    }
+
+    /// Copies an object of type `ty` from the address in `src` to the address in `dst`.
+    fn generate_copy(
+        &mut self,
+        f: &mut BufWriter<File>,
+        ty: &Type,
+        dst: Register,
+        src: Register,
+    ) -> anyhow::Result<()> {
+        for i in 0..ty.size() {
+            writeln!(
+                f,
+                "\tmov {}, byte ptr [{}+{}]",
+                Register::R8.byte(),
+                src.qword(),
+                i
+            )?;
+            writeln!(
+                f,
+                "\tmov byte ptr [{}+{}], {}",
+                dst.qword(),
+                i,
+                Register::R8.byte()
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Sign or zero extends the low bits of `reg` according to `ty`.
+    fn generate_extend(
+        &mut self,
+        f: &mut BufWriter<File>,
+        ty: &Type,
+        reg: Register,
+    ) -> anyhow::Result<()> {
+        match (ty.size(), ty.is_signed()) {
+            (1, true) => writeln!(f, "\tmovsx {}, {}", reg.qword(), reg.byte())?,
+            (1, false) => writeln!(f, "\tmovzx {}, {}", reg.dword(), reg.byte())?,
+            (2, true) => writeln!(f, "\tmovsx {}, {}", reg.qword(), reg.word())?,
+            (2, false) => writeln!(f, "\tmovzx {}, {}", reg.dword(), reg.word())?,
+            (4, true) => writeln!(f, "\tmovsxd {}, {}", reg.qword(), reg.dword())?,
+            (4, false) => writeln!(f, "\tmov {}, {}", reg.dword(), reg.dword())?,
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Moves a floating value from `reg` to `xmm{index}`.
+    fn generate_to_xmm(
+        &mut self,
+        f: &mut BufWriter<File>,
+        reg: Register,
+        index: usize,
+    ) -> anyhow::Result<()> {
+        writeln!(f, "\tmovq xmm{}, {}", index, reg.qword())?;
+        Ok(())
+    }
+
+    /// Moves a floating value of type `ty` from `xmm{index}` to `reg`, clearing the unused bits.
+    fn generate_from_xmm(
+        &mut self,
+        f: &mut BufWriter<File>,
+        ty: &Type,
+        index: usize,
+        reg: Register,
+    ) -> anyhow::Result<()> {
+        if ty.size() == 4 {
+            writeln!(f, "\tmovd {}, xmm{}", reg.dword(), index)?;
+        } else {
+            writeln!(f, "\tmovq {}, xmm{}", reg.qword(), index)?;
+        }
+        Ok(())
+    }
+
+    /// Converts the value in `rax` from `from` to `to`. Floating values are kept as their bits.
+    fn generate_convert(
+        &mut self,
+        f: &mut BufWriter<File>,
+        from: &Type,
+        to: &Type,
+    ) -> anyhow::Result<()> {
+        match (from.is_float(), to.is_float()) {
+            (_, false) if *to == Type::Void => {}
+            (false, false) => self.generate_extend(f, to, Register::Rax)?,
+            (true, true) => {
+                if from.size() != to.size() {
+                    self.generate_to_xmm(f, Register::Rax, 0)?;
+                    writeln!(
+                        f,
+                        "\tcvt{}2{} xmm0, xmm0",
+                        Self::sse_suffix(from),
+                        Self::sse_suffix(to)
+                    )?;
+                    self.generate_from_xmm(f, to, 0, Register::Rax)?;
+                }
+            }
+            (false, true) => {
+                let suffix = Self::sse_suffix(to);
+                if from.size() == 8 && !from.is_signed() {
+                    let label_num = self.label_num();
+                    writeln!(
+                        f,
+                        "\ttest {}, {}",
+                        Register::Rax.qword(),
+                        Register::Rax.qword()
+                    )?;
+                    writeln!(f, "\tjs .Lunsigned{}", label_num)?;
+                    writeln!(f, "\tcvtsi2{} xmm0, {}", suffix, Register::Rax.qword())?;
+                    writeln!(f, "\tjmp .Lend{}", label_num)?;
+                    writeln!(f, ".Lunsigned{}:", label_num)?;
+                    // Halve the value keeping its lowest bit for rounding, then double it back.
+                    writeln!(
+                        f,
+                        "\tmov {}, {}",
+                        Register::Rdx.qword(),
+                        Register::Rax.qword()
+                    )?;
+                    writeln!(f, "\tshr {}, 1", Register::Rdx.qword())?;
+                    writeln!(f, "\tand {}, 1", Register::Rax.dword())?;
+                    writeln!(
+                        f,
+                        "\tor {}, {}",
+                        Register::Rdx.qword(),
+                        Register::Rax.qword()
+                    )?;
+                    writeln!(f, "\tcvtsi2{} xmm0, {}", suffix, Register::Rdx.qword())?;
+                    writeln!(f, "\tadd{} xmm0, xmm0", suffix)?;
+                    writeln!(f, ".Lend{}:", label_num)?;
+                } else {
+                    writeln!(f, "\tcvtsi2{} xmm0, {}", suffix, Register::Rax.qword())?;
+                }
+                self.generate_from_xmm(f, to, 0, Register::Rax)?;
+            }
+            (true, false) => {
+                let suffix = Self::sse_suffix(from);
+                self.generate_to_xmm(f, Register::Rax, 0)?;
+                if to.size() == 8 && !to.is_signed() {
+                    // Values from 2^63 up are converted after subtracting 2^63.
+                    let label_num = self.label_num();
+                    let limit = Self::float_bits(9223372036854775808.0, from);
+                    writeln!(f, "\tmovabs {}, {}", Register::Rdx.qword(), limit)?;
+                    self.generate_to_xmm(f, Register::Rdx, 1)?;
+                    writeln!(f, "\tucomi{} xmm0, xmm1", suffix)?;
+                    writeln!(f, "\tjae .Lunsigned{}", label_num)?;
+                    writeln!(f, "\tcvtt{}2si {}, xmm0", suffix, Register::Rax.qword())?;
+                    writeln!(f, "\tjmp .Lend{}", label_num)?;
+                    writeln!(f, ".Lunsigned{}:", label_num)?;
+                    writeln!(f, "\tsub{} xmm0, xmm1", suffix)?;
+                    writeln!(f, "\tcvtt{}2si {}, xmm0", suffix, Register::Rax.qword())?;
+                    writeln!(f, "\tbtc {}, 63", Register::Rax.qword())?;
+                    writeln!(f, ".Lend{}:", label_num)?;
+                } else {
+                    writeln!(f, "\tcvtt{}2si {}, xmm0", suffix, Register::Rax.qword())?;
+                    self.generate_extend(f, to, Register::Rax)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// The number of stack slots a value of type `ty` takes. A `long double` keeps its 80-bit
+    /// image in two.
+    fn value_slots(ty: &Type) -> usize {
+        if ty.is_long_double() {
+            2
+        } else {
+            1
+        }
+    }
+
+    /// Pushes the `long double` in `st(0)`, popping it off the x87 stack.
+    fn generate_push_x87(&mut self, f: &mut BufWriter<File>) -> anyhow::Result<()> {
+        writeln!(f, "\tsub {}, 16", Register::Rsp.qword())?;
+        writeln!(f, "\tfstp tbyte ptr [{}]", Register::Rsp.qword())?;
+        self.stack += 2;
+        Ok(())
+    }
+
+    /// Pops a `long double` onto the x87 stack.
+    fn generate_pop_x87(&mut self, f: &mut BufWriter<File>) -> anyhow::Result<()> {
+        writeln!(f, "\tfld tbyte ptr [{}]", Register::Rsp.qword())?;
+        writeln!(f, "\tadd {}, 16", Register::Rsp.qword())?;
+        self.stack -= 2;
+        Ok(())
+    }
+
+    /// Pops and discards the value of an expression of type `ty`.
+    fn generate_discard(&mut self, f: &mut BufWriter<File>, ty: &Type) -> anyhow::Result<()> {
+        if ty.is_long_double() {
+            writeln!(f, "\tadd {}, 16", Register::Rsp.qword())?;
+            self.stack -= 2;
+            Ok(())
+        } else {
+            self.generate_pop(f, Register::Rax)
+        }
+    }
+
+    /// Converts the value of type `from` in `rax` to `long double` and loads it onto the x87
+    /// stack.
+    fn generate_x87_load(&mut self, f: &mut BufWriter<File>, from: &Type) -> anyhow::Result<()> {
+        self.generate_push_with_reg(f, Register::Rax)?;
+        match (from.is_float(), from.size()) {
+            (true, 4) => writeln!(f, "\tfld dword ptr [{}]", Register::Rsp.qword())?,
+            (true, _) => writeln!(f, "\tfld qword ptr [{}]", Register::Rsp.qword())?,
+            (false, _) => {
+                writeln!(f, "\tfild qword ptr [{}]", Register::Rsp.qword())?;
+                if from.size() == 8 && !from.is_signed() {
+                    // `fild` reads the value as signed, so add 2^64 back to negative ones.
+                    let label_num = self.label_num();
+                    writeln!(
+                        f,
+                        "\ttest {}, {}",
+                        Register::Rax.qword(),
+                        Register::Rax.qword()
+                    )?;
+                    writeln!(f, "\tjns .Lend{}", label_num)?;
+                    writeln!(
+                        f,
+                        "\tmov dword ptr [{}], {}",
+                        Register::Rsp.qword(),
+                        (18446744073709551616.0f32).to_bits()
+                    )?;
+                    writeln!(f, "\tfadd dword ptr [{}]", Register::Rsp.qword())?;
+                    writeln!(f, ".Lend{}:", label_num)?;
+                }
+            }
+        }
+        self.generate_pop(f, Register::Rax)?;
+        Ok(())
+    }
+
+    /// Pops `st(0)` off the x87 stack and converts it to `to` in `rax`.
+    fn generate_x87_store(&mut self, f: &mut BufWriter<File>, to: &Type) -> anyhow::Result<()> {
+        if *to == Type::Void {
+            writeln!(f, "\tfstp st(0)")?;
+            return Ok(());
+        }
+        if to.is_float() {
+            self.generate_push_with_reg(f, Register::Rax)?;
+            let size = if to.size() == 4 { "dword" } else { "qword" };
+            writeln!(f, "\tfstp {} ptr [{}]", size, Register::Rsp.qword())?;
+            self.generate_pop(f, Register::Rax)?;
+            self.generate_extend(f, to, Register::Rax)?;
+            return Ok(());
+        }
+        // The result and the control words live in two slots. `fistp` rounds by the control
+        // word, so truncation is switched on around it.
+        let rsp = Register::Rsp.qword();
+        self.generate_push_with_reg(f, Register::Rax)?;
+        self.generate_push_with_reg(f, Register::Rax)?;
+        writeln!(f, "\tfnstcw word ptr [{}]", rsp)?;
+        writeln!(f, "\tmovzx {}, word ptr [{}]", Register::Rax.dword(), rsp)?;
+        writeln!(f, "\tor {}, 0xc00", Register::Rax.dword())?;
+        writeln!(f, "\tmov word ptr [{}+2], {}", rsp, Register::Rax.word())?;
+        writeln!(f, "\tfldcw word ptr [{}+2]", rsp)?;
+        if to.size() == 8 && !to.is_signed() {
+            // Values from 2^63 up are converted after subtracting 2^63.
+            let label_num = self.label_num();
+            writeln!(
+                f,
+                "\tmov dword ptr [{}+4], {}",
+                rsp,
+                (9223372036854775808.0f32).to_bits()
+            )?;
+            writeln!(f, "\tfld dword ptr [{}+4]", rsp)?;
+            writeln!(f, "\tfcomip st, st(1)")?;
+            writeln!(f, "\tja .Lsigned{}", label_num)?;
+            writeln!(f, "\tfld dword ptr [{}+4]", rsp)?;
+            writeln!(f, "\tfsubr st, st(1)")?;
+            writeln!(f, "\tfstp st(1)")?;
+            writeln!(f, "\tfistp qword ptr [{}+8]", rsp)?;
+            writeln!(f, "\tbtc qword ptr [{}+8], 63", rsp)?;
+            writeln!(f, "\tjmp .Lend{}", label_num)?;
+            writeln!(f, ".Lsigned{}:", label_num)?;
+            writeln!(f, "\tfistp qword ptr [{}+8]", rsp)?;
+            writeln!(f, ".Lend{}:", label_num)?;
+        } else {
+            writeln!(f, "\tfistp qword ptr [{}+8]", rsp)?;
+        }
+        writeln!(f, "\tfldcw word ptr [{}]", rsp)?;
+        self.generate_pop(f, Register::Rax)?;
+        self.generate_pop(f, Register::Rax)?;
+        self.generate_extend(f, to, Register::Rax)?;
+        Ok(())
+    }
+
+    /// The suffix of scalar SSE instructions on `ty`.
+    fn sse_suffix(ty: &Type) -> &'static str {
+        if ty.size() == 4 {
+            "ss"
+        } else {
+            "sd"
+        }
+    }
+
+    /// The bits of `value` as a value of the floating type `ty`.
+    fn float_bits(value: f64, ty: &Type) -> u64 {
+        if ty.size() == 4 {
+            (value as f32).to_bits() as u64
+        } else {
+            value.to_bits()
+        }
+    }
+
+    /// Writes `line` in AT&T syntax. Intel syntax takes a symbol named like a register, such as
+    /// `si` or `ch`, for the register even when quoted, while AT&T prefixes registers with `%`.
+    fn generate_att(&mut self, f: &mut BufWriter<File>, line: &str) -> anyhow::Result<()> {
+        writeln!(f, ".att_syntax")?;
+        writeln!(f, "{}", line)?;
+        writeln!(f, ".intel_syntax noprefix")?;
+        Ok(())
+    }
+
     fn label_num(&mut self) -> usize {
         let lebel = self.label;
         self.label += 1;
         lebel
     }
+
+    fn return_label(&self) -> String {
+        format!(".L{}_ret", self.function)
+    }
+
+    /// Labels of `goto` targets are qualified by the function, which no other label contains.
+    fn user_label(&self, label: &str) -> String {
+        format!(".Llabel.{}.{}", self.function, label)
+    }
 }
@@ -3,7 +3,11 @@ use std::{fs::File, io::BufWriter, io::Write};
 use anyhow::anyhow;
 
 use crate::{
-    analyze::expr::{BinaryOpKind, Expr, ExprKind, FuncCallKind},
+    analyze::{
+        expr::{BinaryOpKind, Expr, ExprKind, FuncCallKind, UnaryOpKind},
+        types::{align_to, Type},
+        variable::VariableKind,
+    },
     generate::register::Register,
 };
 
@@ -13,14 +17,15 @@ impl Generator {
     pub fn generate_expr(&mut self, f: &mut BufWriter<File>, expr: Expr) -> anyhow::Result<()> {
         match expr.kind {
             ExprKind::Binary { op_kind, lhs, rhs } => {
-                self.generate_expr_binary(f, op_kind, *lhs, *rhs)?;
+                self.generate_expr_binary(f, op_kind, *lhs, *rhs, &expr.ty)?;
             }
             ExprKind::Assign { op_kind, lhs, rhs } => {
-                self.generate_expr_assign(f, op_kind, *lhs, *rhs)?;
+                self.generate_expr_assign(f, op_kind, *lhs, *rhs, expr.ty)?;
             }
             ExprKind::Comma { lhs, rhs } => {
+                let lhs_ty = lhs.ty.clone();
                 self.generate_expr(f, *lhs)?;
-                self.generate_pop(f, Register::Rax)?;
+                self.generate_discard(f, &lhs_ty)?;
                 self.generate_expr(f, *rhs)?;
             }
             ExprKind::Condition {
@@ -28,45 +33,160 @@ impl Generator {
                 then_expr,
                 else_expr,
             } => self.generate_expr_condition(f, *condition, *then_expr, *else_expr)?,
+            ExprKind::Unary {
+                op_kind,
+                expr: inner,
+            } => self.generate_expr_unary(f, op_kind, *inner, &expr.ty)?,
+            ExprKind::Addr { expr } => self.generate_expr_left_var(f, *expr)?,
+            ExprKind::Cast { expr: inner } => self.generate_expr_cast(f, *inner, &expr.ty)?,
             ExprKind::PostfixIncrement { expr } => {
                 self.generate_expr_postfix_increment(f, *expr)?
             }
             ExprKind::PostfixDecrement { expr } => {
                 self.generate_expr_postfix_decrement(f, *expr)?
             }
-            ExprKind::Variable { .. } => {
+            ExprKind::Variable { .. } | ExprKind::Deref { .. } | ExprKind::Member { .. } => {
                 self.generate_expr_var(f, expr)?;
             }
             ExprKind::Number { number } => {
-                self.generate_expr_number(f, number)?;
+                self.generate_expr_number(f, number, &expr.ty)?;
+            }
+            ExprKind::FloatNumber { value } => {
+                let Type::Float { kind } = expr.ty else {
+                    unreachable!()
+                };
+                let bits = value.to_bits(kind);
+                if expr.ty.is_long_double() {
+                    // The 80-bit value is the low 10 bytes of two quadwords.
+                    self.generate_expr_number(f, (bits >> 64) as usize, &Type::new_long())?;
+                    self.generate_expr_number(f, bits as usize, &Type::new_long())?;
+                } else {
+                    self.generate_expr_number(f, bits as usize, &expr.ty)?;
+                }
             }
-            ExprKind::Func { name, args } => self.generate_expr_func(f, name, args)?,
+            ExprKind::Func { name, args } => self.generate_expr_func(f, name, args, expr.ty)?,
         }
         Ok(())
     }
 
+    /// Converts the value of `expr` to `ty`.
+    fn generate_expr_cast(
+        &mut self,
+        f: &mut BufWriter<File>,
+        expr: Expr,
+        ty: &Type,
+    ) -> anyhow::Result<()> {
+        let from = expr.ty.clone();
+        self.generate_expr(f, expr)?;
+        match (from.is_long_double(), ty.is_long_double()) {
+            (true, true) => {}
+            (true, false) => {
+                self.generate_pop_x87(f)?;
+                self.generate_x87_store(f, ty)?;
+                self.generate_push_with_reg(f, Register::Rax)?;
+            }
+            (false, true) => {
+                self.generate_pop(f, Register::Rax)?;
+                self.generate_x87_load(f, &from)?;
+                self.generate_push_x87(f)?;
+            }
+            (false, false) => {
+                self.generate_pop(f, Register::Rax)?;
+                self.generate_convert(f, &from, ty)?;
+                self.generate_push_with_reg(f, Register::Rax)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn generate_expr_unary(
+        &mut self,
+        f: &mut BufWriter<File>,
+        op_kind: UnaryOpKind,
+        expr: Expr,
+        result_ty: &Type,
+    ) -> anyhow::Result<()> {
+        let ty = expr.ty.clone();
+        self.generate_expr(f, expr)?;
+        // Only negation applies to a `long double` itself.
+        if ty.is_long_double() {
+            self.generate_pop_x87(f)?;
+            writeln!(f, "\tfchs")?;
+            self.generate_push_x87(f)?;
+            return Ok(());
+        }
+        self.generate_pop(f, Register::Rax)?;
+        match op_kind {
+            // Negating a floating value flips its sign bit.
+            UnaryOpKind::Neg if ty.is_float() => {
+                writeln!(f, "\tbtc {}, {}", Register::Rax.qword(), ty.size() * 8 - 1)?;
+            }
+            UnaryOpKind::Neg => {
+                writeln!(f, "\tneg {}", Register::Rax.qword())?;
+                self.generate_extend(f, result_ty, Register::Rax)?;
+            }
+            UnaryOpKind::BitNot => {
+                writeln!(f, "\tnot {}", Register::Rax.qword())?;
+                self.generate_extend(f, result_ty, Register::Rax)?;
+            }
+            UnaryOpKind::LogicNot => {
+                writeln!(f, "\tcmp {}, 0", Register::Rax.qword())?;
+                writeln!(f, "\tsete {}", Register::Rax.byte())?;
+                writeln!(
+                    f,
+                    "\tmovzb {}, {}",
+                    Register::Rax.qword(),
+                    Register::Rax.byte()
+                )?;
+            }
+        }
+        self.generate_push_with_reg(f, Register::Rax)?;
+        Ok(())
+    }
+
     fn generate_expr_binary(
         &mut self,
         f: &mut BufWriter<File>,
         op_kind: BinaryOpKind,
         lhs: Expr,
         rhs: Expr,
+        result_ty: &Type,
     ) -> anyhow::Result<()> {
+        // A shift is done in the promoted type of its left operand, the others in the common type
+        // of both.
+        let ty = match op_kind {
+            BinaryOpKind::LShift | BinaryOpKind::RShift => result_ty.clone(),
+            _ => lhs.ty.clone(),
+        };
         self.generate_expr(f, lhs)?;
         self.generate_expr(f, rhs)?;
+        if ty.is_long_double() {
+            self.generate_pop_x87(f)?;
+            self.generate_pop_x87(f)?;
+            return self.generate_x87_binary(f, op_kind);
+        }
         self.generate_pop(f, Register::Rdi)?;
         self.generate_pop(f, Register::Rax)?;
-        self.generate_expr_binary_with_reg(f, op_kind, Register::Rax, Register::Rdi)?;
+        self.generate_expr_binary_with_reg(f, op_kind, &ty, Register::Rax, Register::Rdi)?;
         Ok(())
     }
 
+    /// Applies `op_kind` to operands of type `ty` and pushes the result. Unsigned operands use
+    /// unsigned division, logical shifts and unsigned comparisons, and results are extended again
+    /// to their type.
     fn generate_expr_binary_with_reg(
         &mut self,
         f: &mut BufWriter<File>,
         op_kind: BinaryOpKind,
+        ty: &Type,
         lhs: Register,
         rhs: Register,
     ) -> anyhow::Result<()> {
+        if ty.is_float() {
+            self.generate_float_binary(f, op_kind, ty, lhs, rhs)?;
+            self.generate_push_with_reg(f, Register::Rax)?;
+            return Ok(());
+        }
         if lhs != Register::Rax {
             writeln!(f, "\tmov {}, {}", Register::Rax.qword(), lhs.qword())?;
         }
@@ -99,12 +219,10 @@ impl Generator {
                 )?;
             }
             BinaryOpKind::Div => {
-                writeln!(f, "\tcqo")?;
-                writeln!(f, "\tidiv {}", Register::Rdi.qword())?;
+                self.generate_div(f, ty)?;
             }
             BinaryOpKind::Rem => {
-                writeln!(f, "\tcqo")?;
-                writeln!(f, "\tidiv {}", Register::Rdi.qword())?;
+                self.generate_div(f, ty)?;
                 writeln!(
                     f,
                     "\tmov {}, {}",
@@ -157,9 +275,11 @@ impl Generator {
                     Register::Rcx.qword(),
                     Register::Rdi.qword()
                 )?;
+                let op = if ty.is_signed() { "sar" } else { "shr" };
                 writeln!(
                     f,
-                    "\tshr {}, {}",
+                    "\t{} {}, {}",
+                    op,
                     Register::Rax.qword(),
                     Register::Rcx.byte()
                 )?;
@@ -171,7 +291,8 @@ impl Generator {
                     Register::Rax.qword(),
                     Register::Rdi.qword()
                 )?;
-                writeln!(f, "\tsetl {}", Register::Rax.byte())?;
+                let set = if ty.is_signed() { "setl" } else { "setb" };
+                writeln!(f, "\t{} {}", set, Register::Rax.byte())?;
                 writeln!(
                     f,
                     "\tmovzb {}, {}",
@@ -186,7 +307,8 @@ impl Generator {
                     Register::Rax.qword(),
                     Register::Rdi.qword()
                 )?;
-                writeln!(f, "\tsetle {}", Register::Rax.byte())?;
+                let set = if ty.is_signed() { "setle" } else { "setbe" };
+                writeln!(f, "\t{} {}", set, Register::Rax.byte())?;
                 writeln!(
                     f,
                     "\tmovzb {}, {}",
@@ -225,50 +347,253 @@ impl Generator {
                 )?;
             }
         }
+        if !op_kind.is_comparison() {
+            self.generate_extend(f, ty, Register::Rax)?;
+        }
         self.generate_push_with_reg(f, Register::Rax)?;
         Ok(())
     }
 
+    /// Divides `rax` by `rdi` as operands of type `ty`, leaving the quotient in `rax` and the
+    /// remainder in `rdx`.
+    fn generate_div(&mut self, f: &mut BufWriter<File>, ty: &Type) -> anyhow::Result<()> {
+        if ty.is_signed() {
+            writeln!(f, "\tcqo")?;
+            writeln!(f, "\tidiv {}", Register::Rdi.qword())?;
+        } else {
+            writeln!(
+                f,
+                "\txor {}, {}",
+                Register::Rdx.dword(),
+                Register::Rdx.dword()
+            )?;
+            writeln!(f, "\tdiv {}", Register::Rdi.qword())?;
+        }
+        Ok(())
+    }
+
+    /// Applies `op_kind` to floating operands in `lhs` and `rhs`, leaving the result in `rax`.
+    /// Comparisons test the flags so that they are false on NaN, except `!=`.
+    fn generate_float_binary(
+        &mut self,
+        f: &mut BufWriter<File>,
+        op_kind: BinaryOpKind,
+        ty: &Type,
+        lhs: Register,
+        rhs: Register,
+    ) -> anyhow::Result<()> {
+        let suffix = Self::sse_suffix(ty);
+        self.generate_to_xmm(f, lhs, 0)?;
+        self.generate_to_xmm(f, rhs, 1)?;
+        let op = match op_kind {
+            BinaryOpKind::Add => "add",
+            BinaryOpKind::Sub => "sub",
+            BinaryOpKind::Mul => "mul",
+            BinaryOpKind::Div => "div",
+            BinaryOpKind::Lt | BinaryOpKind::LtEqual => {
+                // `a < b` is `b > a`, which is false on unordered operands.
+                writeln!(f, "\tucomi{} xmm1, xmm0", suffix)?;
+                let set = if op_kind == BinaryOpKind::Lt {
+                    "seta"
+                } else {
+                    "setae"
+                };
+                writeln!(f, "\t{} {}", set, Register::Rax.byte())?;
+                writeln!(
+                    f,
+                    "\tmovzb {}, {}",
+                    Register::Rax.qword(),
+                    Register::Rax.byte()
+                )?;
+                return Ok(());
+            }
+            BinaryOpKind::Equal | BinaryOpKind::NotEqual => {
+                writeln!(f, "\tucomi{} xmm0, xmm1", suffix)?;
+                let (set, parity, combine) = if op_kind == BinaryOpKind::Equal {
+                    ("sete", "setnp", "and")
+                } else {
+                    ("setne", "setp", "or")
+                };
+                writeln!(f, "\t{} {}", set, Register::Rax.byte())?;
+                writeln!(f, "\t{} {}", parity, Register::Rdx.byte())?;
+                writeln!(
+                    f,
+                    "\t{} {}, {}",
+                    combine,
+                    Register::Rax.byte(),
+                    Register::Rdx.byte()
+                )?;
+                writeln!(
+                    f,
+                    "\tmovzb {}, {}",
+                    Register::Rax.qword(),
+                    Register::Rax.byte()
+                )?;
+                return Ok(());
+            }
+            _ => unreachable!(),
+        };
+        writeln!(f, "\t{}{} xmm0, xmm1", op, suffix)?;
+        self.generate_from_xmm(f, ty, 0, Register::Rax)?;
+        Ok(())
+    }
+
+    /// Applies `op_kind` to `st(0)` and `st(1)` as the left and right operands, popping both, and
+    /// pushes the result.
+    fn generate_x87_binary(
+        &mut self,
+        f: &mut BufWriter<File>,
+        op_kind: BinaryOpKind,
+    ) -> anyhow::Result<()> {
+        match op_kind {
+            BinaryOpKind::Add | BinaryOpKind::Sub | BinaryOpKind::Mul | BinaryOpKind::Div => {
+                self.generate_x87_arith(f, op_kind)?;
+                self.generate_push_x87(f)
+            }
+            _ => {
+                // Compare the right operand with the left one, as `generate_float_binary` does.
+                writeln!(f, "\tfxch st(1)")?;
+                writeln!(f, "\tfucomip st, st(1)")?;
+                writeln!(f, "\tfstp st(0)")?;
+                let (set, parity, combine) = match op_kind {
+                    BinaryOpKind::Lt => ("seta", None, ""),
+                    BinaryOpKind::LtEqual => ("setae", None, ""),
+                    BinaryOpKind::Equal => ("sete", Some("setnp"), "and"),
+                    BinaryOpKind::NotEqual => ("setne", Some("setp"), "or"),
+                    _ => unreachable!(),
+                };
+                writeln!(f, "\t{} {}", set, Register::Rax.byte())?;
+                if let Some(parity) = parity {
+                    writeln!(f, "\t{} {}", parity, Register::Rdx.byte())?;
+                    writeln!(
+                        f,
+                        "\t{} {}, {}",
+                        combine,
+                        Register::Rax.byte(),
+                        Register::Rdx.byte()
+                    )?;
+                }
+                writeln!(
+                    f,
+                    "\tmovzb {}, {}",
+                    Register::Rax.qword(),
+                    Register::Rax.byte()
+                )?;
+                self.generate_push_with_reg(f, Register::Rax)
+            }
+        }
+    }
+
+    /// Replaces `st(0)` and `st(1)` with the result of `op_kind` on them as the left and right
+    /// operands.
+    fn generate_x87_arith(
+        &mut self,
+        f: &mut BufWriter<File>,
+        op_kind: BinaryOpKind,
+    ) -> anyhow::Result<()> {
+        let op = match op_kind {
+            BinaryOpKind::Add => "fadd",
+            BinaryOpKind::Sub => "fsub",
+            BinaryOpKind::Mul => "fmul",
+            BinaryOpKind::Div => "fdiv",
+            _ => unreachable!(),
+        };
+        writeln!(f, "\t{} st, st(1)", op)?;
+        writeln!(f, "\tfstp st(1)")?;
+        Ok(())
+    }
+
     fn generate_expr_assign(
         &mut self,
         f: &mut BufWriter<File>,
         op_kind: BinaryOpKind,
         lhs: Expr,
         rhs: Expr,
+        ty: Type,
     ) -> anyhow::Result<()> {
-        if op_kind == BinaryOpKind::Equal {
+        if ty.is_long_double() || rhs.ty.is_long_double() {
+            return self.generate_x87_assign(f, op_kind, lhs, rhs, ty);
+        }
+        if op_kind != BinaryOpKind::Equal {
+            // The operation is done in the type of `rhs`, the common type of the operands, except
+            // that a shift is done in the promoted type of `lhs`.
+            let op_ty = match op_kind {
+                BinaryOpKind::LShift | BinaryOpKind::RShift => ty.promote(),
+                _ => rhs.ty.clone(),
+            };
             self.generate_expr_left_var(f, lhs)?;
             self.generate_expr(f, rhs)?;
             self.generate_pop(f, Register::Rdi)?;
             self.generate_pop(f, Register::Rax)?;
-            writeln!(
-                f,
-                "\tmov {}, [{}]",
-                Register::R8.qword(),
-                Register::Rax.qword()
-            )?;
             self.generate_push_with_reg(f, Register::Rax)?;
-            self.generate_expr_binary_with_reg(f, op_kind, Register::R8, Register::Rdi)?;
-            self.generate_pop(f, Register::Rdi)?;
+            self.generate_load(f, &ty, Register::Rax, Register::Rax.qword())?;
+            self.generate_convert(f, &ty, &op_ty)?;
+            self.generate_expr_binary_with_reg(f, op_kind, &op_ty, Register::Rax, Register::Rdi)?;
             self.generate_pop(f, Register::Rax)?;
+            self.generate_convert(f, &op_ty, &ty)?;
             writeln!(
                 f,
-                "\tmov [{}], {}",
-                Register::Rax.qword(),
-                Register::Rdi.qword()
+                "\tmov {}, {}",
+                Register::Rdi.qword(),
+                Register::Rax.qword()
             )?;
-            self.generate_push_with_reg(f, Register::Rdi)?;
+            self.generate_pop(f, Register::Rax)?;
         } else {
             self.generate_expr_left_var(f, lhs)?;
             self.generate_expr(f, rhs)?;
             self.generate_pop(f, Register::Rdi)?;
             self.generate_pop(f, Register::Rax)?;
+            if ty.is_struct() {
+                self.generate_copy(f, &ty, Register::Rax, Register::Rdi)?;
+                self.generate_push_with_reg(f, Register::Rax)?;
+                return Ok(());
+            }
+        }
+        self.generate_store(f, &ty, Register::Rax.qword(), Register::Rdi)?;
+        self.generate_extend(f, &ty, Register::Rdi)?;
+        self.generate_push_with_reg(f, Register::Rdi)?;
+        Ok(())
+    }
+
+    /// Assigns where `lhs` or the operation is a `long double`, computing on the x87 stack.
+    fn generate_x87_assign(
+        &mut self,
+        f: &mut BufWriter<File>,
+        op_kind: BinaryOpKind,
+        lhs: Expr,
+        rhs: Expr,
+        ty: Type,
+    ) -> anyhow::Result<()> {
+        // A compound operation is done in `long double`, the common type of the operands.
+        self.generate_expr_left_var(f, lhs)?;
+        self.generate_expr(f, rhs)?;
+        self.generate_pop_x87(f)?;
+        if op_kind != BinaryOpKind::Equal {
+            self.generate_pop(f, Register::Rax)?;
+            self.generate_push_with_reg(f, Register::Rax)?;
+            if ty.is_long_double() {
+                writeln!(f, "\tfld tbyte ptr [{}]", Register::Rax.qword())?;
+            } else {
+                self.generate_load(f, &ty, Register::Rax, Register::Rax.qword())?;
+                self.generate_x87_load(f, &ty)?;
+            }
+            self.generate_x87_arith(f, op_kind)?;
+        }
+        if ty.is_long_double() {
+            self.generate_pop(f, Register::Rax)?;
+            writeln!(f, "\tfstp tbyte ptr [{}]", Register::Rax.qword())?;
+            writeln!(f, "\tfld tbyte ptr [{}]", Register::Rax.qword())?;
+            self.generate_push_x87(f)?;
+        } else {
+            self.generate_x87_store(f, &ty)?;
             writeln!(
                 f,
-                "\tmov [{}], {}",
-                Register::Rax.qword(),
-                Register::Rdi.qword()
+                "\tmov {}, {}",
+                Register::Rdi.qword(),
+                Register::Rax.qword()
             )?;
+            self.generate_pop(f, Register::Rax)?;
+            self.generate_store(f, &ty, Register::Rax.qword(), Register::Rdi)?;
             self.generate_push_with_reg(f, Register::Rdi)?;
         }
         Ok(())
@@ -282,12 +607,15 @@ impl Generator {
         else_expr: Expr,
     ) -> anyhow::Result<()> {
         let label_num = self.label_num();
+        let slots = Self::value_slots(&then_expr.ty);
         self.generate_expr(f, condition)?;
         self.generate_pop(f, Register::Rax)?;
         writeln!(f, "\tcmp {}, 0", Register::Rax.qword())?;
         writeln!(f, "\tje .Lelse{}", label_num)?;
         self.generate_expr(f, then_expr)?;
         writeln!(f, "\tjmp .Lend{}", label_num)?;
+        // Only one of the branches runs, so count its push once.
+        self.stack -= slots;
         writeln!(f, ".Lelse{}:", label_num)?;
         self.generate_expr(f, else_expr)?;
         writeln!(f, ".Lend{}:", label_num)?;
@@ -299,22 +627,16 @@ impl Generator {
         f: &mut BufWriter<File>,
         expr: Expr,
     ) -> anyhow::Result<()> {
+        let ty = expr.ty.clone();
         self.generate_expr_left_var(f, expr)?;
         self.generate_pop(f, Register::Rdi)?;
-        writeln!(
-            f,
-            "\tmov {}, [{}]",
-            Register::Rax.qword(),
-            Register::Rdi.qword()
-        )?;
+        if ty.is_long_double() {
+            return self.generate_x87_step(f, BinaryOpKind::Add);
+        }
+        self.generate_load(f, &ty, Register::Rax, Register::Rdi.qword())?;
         self.generate_push_with_reg(f, Register::Rax)?;
-        writeln!(f, "\tadd {}, 1", Register::Rax.qword())?;
-        writeln!(
-            f,
-            "\tmov [{}], {}",
-            Register::Rdi.qword(),
-            Register::Rax.qword()
-        )?;
+        self.generate_step(f, &ty, BinaryOpKind::Add)?;
+        self.generate_store(f, &ty, Register::Rdi.qword(), Register::Rax)?;
         Ok(())
     }
 
@@ -323,22 +645,53 @@ impl Generator {
         f: &mut BufWriter<File>,
         expr: Expr,
     ) -> anyhow::Result<()> {
+        let ty = expr.ty.clone();
         self.generate_expr_left_var(f, expr)?;
         self.generate_pop(f, Register::Rdi)?;
-        writeln!(
-            f,
-            "\tmov {}, [{}]",
-            Register::Rax.qword(),
-            Register::Rdi.qword()
-        )?;
+        if ty.is_long_double() {
+            return self.generate_x87_step(f, BinaryOpKind::Sub);
+        }
+        self.generate_load(f, &ty, Register::Rax, Register::Rdi.qword())?;
         self.generate_push_with_reg(f, Register::Rax)?;
-        writeln!(f, "\tsub {}, 1", Register::Rax.qword())?;
-        writeln!(
-            f,
-            "\tmov [{}], {}",
-            Register::Rdi.qword(),
-            Register::Rax.qword()
-        )?;
+        self.generate_step(f, &ty, BinaryOpKind::Sub)?;
+        self.generate_store(f, &ty, Register::Rdi.qword(), Register::Rax)?;
+        Ok(())
+    }
+
+    /// Adds or subtracts one to the value of type `ty` in `rax`, scaled for pointers.
+    fn generate_step(
+        &mut self,
+        f: &mut BufWriter<File>,
+        ty: &Type,
+        op_kind: BinaryOpKind,
+    ) -> anyhow::Result<()> {
+        if ty.is_float() {
+            let one = Self::float_bits(1.0, ty);
+            writeln!(f, "\tmovabs {}, {}", Register::Rdx.qword(), one)?;
+            return self.generate_float_binary(f, op_kind, ty, Register::Rax, Register::Rdx);
+        }
+        let op = if op_kind == BinaryOpKind::Add {
+            "add"
+        } else {
+            "sub"
+        };
+        let delta = ty.pointee().map_or(1, |ty| ty.size());
+        writeln!(f, "\t{} {}, {}", op, Register::Rax.qword(), delta)?;
+        Ok(())
+    }
+
+    /// Pushes the `long double` at the address in `rdi` and adds or subtracts one to it.
+    fn generate_x87_step(
+        &mut self,
+        f: &mut BufWriter<File>,
+        op_kind: BinaryOpKind,
+    ) -> anyhow::Result<()> {
+        writeln!(f, "\tfld tbyte ptr [{}]", Register::Rdi.qword())?;
+        self.generate_push_x87(f)?;
+        writeln!(f, "\tfld1")?;
+        writeln!(f, "\tfld tbyte ptr [{}]", Register::Rdi.qword())?;
+        self.generate_x87_arith(f, op_kind)?;
+        writeln!(f, "\tfstp tbyte ptr [{}]", Register::Rdi.qword())?;
         Ok(())
     }
 
@@ -347,24 +700,60 @@ impl Generator {
         f: &mut BufWriter<File>,
         name: FuncCallKind,
         args: Vec<Expr>,
+        ty: Type,
     ) -> anyhow::Result<()> {
-        let arg_len = args.len();
-        let stack: usize = if 6 < arg_len { arg_len - 6 } else { 0 };
+        // Floating arguments go in xmm0-xmm7 and the others in six general-purpose registers.
+        // The rest, and every `long double`, are passed on the stack in order.
+        let (mut reg_args, mut stack_args) = (Vec::new(), Vec::new());
+        let (mut gp, mut fp) = (0, 0);
+        for arg in args.into_iter() {
+            if arg.ty.is_long_double() {
+                stack_args.push(arg);
+                continue;
+            }
+            let count = if arg.ty.is_float() { &mut fp } else { &mut gp };
+            let limit = if arg.ty.is_float() { 8 } else { 6 };
+            if *count < limit {
+                *count += 1;
+                reg_args.push(arg);
+            } else {
+                stack_args.push(arg);
+            }
+        }
+        // A `long double` on the stack is aligned to 16 bytes.
+        let mut offsets = Vec::new();
+        let mut size = 0;
+        for arg in stack_args.iter() {
+            size = align_to(size, arg.ty.align().max(8));
+            offsets.push(size);
+            size += Self::value_slots(&arg.ty) * 8;
+        }
+        let stack = size / 8;
         let stack_adjust = (self.stack + stack) % 2 == 1;
         if stack_adjust {
             writeln!(f, "\tsub {}, 8", Register::Rsp.qword())?;
             self.stack += 1;
         }
-        self.generate_expr_func_args(f, args)?;
+        let arg_types: Vec<Type> = reg_args.iter().map(|arg| arg.ty.clone()).collect();
+        for (arg, offset) in stack_args.into_iter().zip(offsets).rev() {
+            let padding = size - offset - Self::value_slots(&arg.ty) * 8;
+            if padding > 0 {
+                writeln!(f, "\tsub {}, {}", Register::Rsp.qword(), padding)?;
+                self.stack += padding / 8;
+            }
+            size = offset;
+            self.generate_expr(f, arg)?;
+        }
+        self.generate_expr_func_args(f, reg_args)?;
         match name {
             FuncCallKind::Label { name } => {
-                self.generate_set_func_args(f, arg_len)?;
-                writeln!(f, "\tcall {}", name)?;
+                self.generate_set_func_args(f, &arg_types)?;
+                self.generate_att(f, &format!("\tcall {}", name))?;
             }
             FuncCallKind::Expr { expr } => {
                 self.generate_expr(f, *expr)?;
                 self.generate_pop(f, Register::R10)?;
-                self.generate_set_func_args(f, arg_len)?;
+                self.generate_set_func_args(f, &arg_types)?;
                 writeln!(f, "\tcall {}", Register::R10.qword())?;
             }
         };
@@ -375,6 +764,15 @@ impl Generator {
             writeln!(f, "\tadd {}, {}", Register::Rsp.qword(), stack * 8)?;
             self.stack -= stack;
         }
+        // A `long double` is returned in `st(0)`.
+        if ty.is_long_double() {
+            return self.generate_push_x87(f);
+        }
+        if ty.is_float() {
+            self.generate_from_xmm(f, &ty, 0, Register::Rax)?;
+        } else {
+            self.generate_extend(f, &ty, Register::Rax)?;
+        }
         self.generate_push_with_reg(f, Register::Rax)?;
         Ok(())
     }
@@ -390,12 +788,13 @@ impl Generator {
         Ok(())
     }
 
+    /// Pops the register arguments of types `arg_types` into their registers.
     fn generate_set_func_args(
         &mut self,
         f: &mut BufWriter<File>,
-        length: usize,
+        arg_types: &[Type],
     ) -> anyhow::Result<()> {
-        let regs = vec![
+        let regs = [
             Register::Rdi,
             Register::Rsi,
             Register::Rdx,
@@ -403,9 +802,19 @@ impl Generator {
             Register::R8,
             Register::R9,
         ];
-        for reg in regs.iter().take(std::cmp::min(length, 6)) {
-            self.generate_pop(f, reg.clone())?;
+        let (mut gp, mut fp) = (0, 0);
+        for ty in arg_types.iter() {
+            if ty.is_float() {
+                self.generate_pop(f, Register::Rax)?;
+                self.generate_to_xmm(f, Register::Rax, fp)?;
+                fp += 1;
+            } else {
+                self.generate_pop(f, regs[gp].clone())?;
+                gp += 1;
+            }
         }
+        // A variadic callee reads the number of vector registers used from `al`.
+        writeln!(f, "\tmov {}, {}", Register::Rax.dword(), fp)?;
         Ok(())
     }
 
@@ -416,13 +825,33 @@ impl Generator {
     ) -> anyhow::Result<()> {
         match expr.kind {
             ExprKind::Variable { var } => {
-                writeln!(
-                    f,
-                    "\tmov {}, {}",
-                    Register::Rax.qword(),
-                    Register::Rbp.qword()
-                )?;
-                writeln!(f, "\tsub {}, {}", Register::Rax.qword(), var.offset)?;
+                match var.kind {
+                    VariableKind::Local { offset } => {
+                        writeln!(
+                            f,
+                            "\tmov {}, {}",
+                            Register::Rax.qword(),
+                            Register::Rbp.qword()
+                        )?;
+                        writeln!(f, "\tsub {}, {}", Register::Rax.qword(), offset)?;
+                    }
+                    VariableKind::Global { name } => {
+                        self.generate_att(
+                            f,
+                            &format!("\tleaq {}(%rip), %{}", name, Register::Rax.qword()),
+                        )?;
+                    }
+                }
+                self.generate_push_with_reg(f, Register::Rax)?;
+            }
+            ExprKind::Deref { expr } => {
+                self.generate_expr(f, *expr)?;
+            }
+            ExprKind::Member { expr, offset } => {
+                self.generate_expr(f, *expr)?;
+                self.generate_pop(f, Register::Rax)?;
+                writeln!(f, "\tadd {}, {}", Register::Rax.qword(), offset)?;
+                self.generate_push_with_reg(f, Register::Rax)?;
             }
             _ => {
                 return Err(anyhow!(
@@ -431,19 +860,23 @@ impl Generator {
                 ))
             }
         }
-        self.generate_push_with_reg(f, Register::Rax)?;
         Ok(())
     }
 
+    /// Loads the value of an lvalue. A struct evaluates to its address.
     fn generate_expr_var(&mut self, f: &mut BufWriter<File>, expr: Expr) -> anyhow::Result<()> {
+        let ty = expr.ty.clone();
         self.generate_expr_left_var(f, expr)?;
+        // A `void` lvalue, such as `*p` for a `void *p`, is only discarded, so nothing is read.
+        if ty.is_struct() || ty == Type::Void {
+            return Ok(());
+        }
         self.generate_pop(f, Register::Rax)?;
-        writeln!(
-            f,
-            "\tmov {}, [{}]",
-            Register::Rax.qword(),
-            Register::Rax.qword()
-        )?;
+        if ty.is_long_double() {
+            writeln!(f, "\tfld tbyte ptr [{}]", Register::Rax.qword())?;
+            return self.generate_push_x87(f);
+        }
+        self.generate_load(f, &ty, Register::Rax, Register::Rax.qword())?;
         self.generate_push_with_reg(f, Register::Rax)?;
         Ok(())
     }
@@ -452,9 +885,19 @@ impl Generator {
         &mut self,
         f: &mut BufWriter<File>,
         number: usize,
+        ty: &Type,
     ) -> anyhow::Result<()> {
-        let number = number as i32;
-        self.generate_push_with_num(f, number)?;
+        // `push` sign-extends its 32-bit immediate, so other values go through `movabs`.
+        match i32::try_from(number as i64) {
+            Ok(number) if ty.is_signed() || number >= 0 || ty.size() == 8 => {
+                self.generate_push_with_num(f, number)?;
+            }
+            _ => {
+                writeln!(f, "\tmovabs {}, {}", Register::Rax.qword(), number as i64)?;
+                self.generate_extend(f, ty, Register::Rax)?;
+                self.generate_push_with_reg(f, Register::Rax)?;
+            }
+        }
         Ok(())
     }
 }
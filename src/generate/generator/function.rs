@@ -0,0 +1,94 @@
+use std::{fs::File, io::BufWriter, io::Write};
+
+use crate::{
+    analyze::{function::Function, types::align_to, variable::VariableKind},
+    generate::register::Register,
+};
+
+use super::Generator;
+
+impl Generator {
+    pub fn generate_function(
+        &mut self,
+        f: &mut BufWriter<File>,
+        function: Function,
+    ) -> anyhow::Result<()> {
+        self.function = function.name;
+        self.stack = 0;
+        if !function.is_static {
+            writeln!(f, ".globl {}", self.function)?;
+        }
+        writeln!(f, "{}:", self.function)?;
+        writeln!(f, "\tpush {}", Register::Rbp.qword())?;
+        writeln!(
+            f,
+            "\tmov {}, {}",
+            Register::Rbp.qword(),
+            Register::Rsp.qword()
+        )?;
+        let offset = function.offset.div_ceil(16) * 16;
+        writeln!(f, "\tsub {}, {}", Register::Rsp.qword(), offset)?;
+
+        let regs = [
+            Register::Rdi,
+            Register::Rsi,
+            Register::Rdx,
+            Register::Rcx,
+            Register::R8,
+            Register::R9,
+        ];
+        let (mut gp, mut fp, mut stack) = (0, 0, 0);
+        for param in function.params.into_iter() {
+            let VariableKind::Local { offset } = param.kind else {
+                unreachable!()
+            };
+            let addr = format!("{}-{}", Register::Rbp.qword(), offset);
+            if param.ty.is_long_double() {
+                stack = align_to(stack, 16);
+                writeln!(
+                    f,
+                    "\tfld tbyte ptr [{}+{}]",
+                    Register::Rbp.qword(),
+                    16 + stack
+                )?;
+                writeln!(f, "\tfstp tbyte ptr [{}]", addr)?;
+                stack += 16;
+            } else if param.ty.is_float() && fp < 8 {
+                let (mov, size) = if param.ty.size() == 4 {
+                    ("movss", "dword")
+                } else {
+                    ("movsd", "qword")
+                };
+                writeln!(f, "\t{} {} ptr [{}], xmm{}", mov, size, addr, fp)?;
+                fp += 1;
+            } else if !param.ty.is_float() && gp < regs.len() {
+                self.generate_store(f, &param.ty, &addr, regs[gp].clone())?;
+                gp += 1;
+            } else {
+                writeln!(
+                    f,
+                    "\tmov {}, [{}+{}]",
+                    Register::Rax.qword(),
+                    Register::Rbp.qword(),
+                    16 + stack
+                )?;
+                self.generate_store(f, &param.ty, &addr, Register::Rax)?;
+                stack += 8;
+            }
+        }
+
+        self.generate_stmt(f, function.body)?;
+
+        writeln!(f, "\tmov {}, 0", Register::Rax.qword())?;
+        writeln!(f, "{}:", self.return_label())?;
+        writeln!(
+            f,
+            "\tmov {}, {}",
+            Register::Rsp.qword(),
+            Register::Rbp.qword()
+        )?;
+        writeln!(f, "\tpop {}", Register::Rbp.qword())?;
+        writeln!(f, "\tret")?;
+        Ok(())
+    }
+}
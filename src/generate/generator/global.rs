@@ -0,0 +1,133 @@
+use std::{fs::File, io::BufWriter, io::Write};
+
+use crate::analyze::{
+    global_variable::{GlobalInit, GlobalVariable},
+    types::Type,
+};
+
+use super::Generator;
+
+impl Generator {
+    pub fn generate_global(
+        &mut self,
+        f: &mut BufWriter<File>,
+        global: GlobalVariable,
+    ) -> anyhow::Result<()> {
+        if global.is_extern {
+            return Ok(());
+        }
+        let init = match global.init {
+            Some(ref init) if !global.is_zero() => init,
+            _ => {
+                if global.is_const {
+                    writeln!(f, ".section .rodata")?;
+                } else {
+                    writeln!(f, ".bss")?;
+                }
+                self.generate_global_label(f, &global)?;
+                writeln!(f, "\t.zero {}", global.ty.size())?;
+                return Ok(());
+            }
+        };
+        // Relocated addresses must stay writable for the dynamic loader.
+        if global.is_const && init.relocations.is_empty() {
+            writeln!(f, ".section .rodata")?;
+        } else {
+            writeln!(f, ".data")?;
+        }
+        self.generate_global_label(f, &global)?;
+        self.generate_global_data(f, &global.ty, 0, init)?;
+        Ok(())
+    }
+
+    /// Emits the initial value of an object with one directive per scalar.
+    fn generate_global_data(
+        &mut self,
+        f: &mut BufWriter<File>,
+        ty: &Type,
+        offset: usize,
+        init: &GlobalInit,
+    ) -> anyhow::Result<()> {
+        match ty {
+            Type::Array { ty: elem, len } => {
+                for index in 0..*len {
+                    self.generate_global_data(f, elem, offset + index * elem.size(), init)?;
+                }
+            }
+            Type::Struct { struct_type } if !struct_type.is_union => {
+                let mut end = offset;
+                for member in struct_type.members().iter() {
+                    let member_offset = offset + member.offset;
+                    if member_offset > end {
+                        writeln!(f, "\t.zero {}", member_offset - end)?;
+                    }
+                    self.generate_global_data(f, &member.ty, member_offset, init)?;
+                    end = member_offset + member.ty.size();
+                }
+                if offset + ty.size() > end {
+                    writeln!(f, "\t.zero {}", offset + ty.size() - end)?;
+                }
+            }
+            ty if ty.is_scalar() => {
+                if let Some(relocation) = init
+                    .relocations
+                    .iter()
+                    .find(|relocation| relocation.offset == offset)
+                {
+                    self.generate_att(
+                        f,
+                        &format!("\t.quad {}{:+}", relocation.label, relocation.addend),
+                    )?;
+                    return Ok(());
+                }
+                // A `long double` takes two quadwords.
+                let size = ty.size().min(8);
+                let directive = match size {
+                    1 => ".byte",
+                    2 => ".short",
+                    4 => ".long",
+                    _ => ".quad",
+                };
+                for start in (offset..offset + ty.size()).step_by(size) {
+                    let mut bytes = [0; 8];
+                    bytes[..size].copy_from_slice(&init.bytes[start..start + size]);
+                    writeln!(f, "\t{} {}", directive, u64::from_le_bytes(bytes))?;
+                }
+            }
+            // Any member of a union may have been initialized, so emit its bytes as they are.
+            _ => {
+                let mut index = offset;
+                while index < offset + ty.size() {
+                    if let Some(relocation) = init
+                        .relocations
+                        .iter()
+                        .find(|relocation| relocation.offset == index)
+                    {
+                        self.generate_att(
+                            f,
+                            &format!("\t.quad {}{:+}", relocation.label, relocation.addend),
+                        )?;
+                        index += 8;
+                    } else {
+                        writeln!(f, "\t.byte {}", init.bytes[index])?;
+                        index += 1;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn generate_global_label(
+        &mut self,
+        f: &mut BufWriter<File>,
+        global: &GlobalVariable,
+    ) -> anyhow::Result<()> {
+        if !global.is_static {
+            writeln!(f, ".globl {}", global.name)?;
+        }
+        writeln!(f, "\t.align {}", global.ty.align())?;
+        writeln!(f, "{}:", global.name)?;
+        Ok(())
+    }
+}
@@ -4,6 +4,7 @@ use crate::{
     analyze::{
         expr::Expr,
         stmt::{Stmt, StmtKind},
+        variable::VariableKind,
     },
     generate::register::Register,
 };
@@ -32,7 +33,7 @@ impl Generator {
                 delta_expr,
                 run_stmt,
             } => {
-                self.generate_stmt_for(f, init_expr, condition_expr, delta_expr, *run_stmt)?;
+                self.generate_stmt_for(f, *init_expr, condition_expr, *delta_expr, *run_stmt)?;
             }
             StmtKind::While {
                 condition_expr,
@@ -40,6 +41,63 @@ impl Generator {
             } => {
                 self.generate_stmt_while(f, condition_expr, *run_stmt)?;
             }
+            StmtKind::DoWhile {
+                run_stmt,
+                condition_expr,
+            } => {
+                self.generate_stmt_do_while(f, *run_stmt, condition_expr)?;
+            }
+            StmtKind::ZeroFill { var } => {
+                let VariableKind::Local { offset } = var.kind else {
+                    unreachable!()
+                };
+                writeln!(
+                    f,
+                    "\tlea {}, [{}-{}]",
+                    Register::Rdi.qword(),
+                    Register::Rbp.qword(),
+                    offset
+                )?;
+                writeln!(f, "\tmov {}, {}", Register::Rcx.qword(), var.ty.size())?;
+                writeln!(
+                    f,
+                    "\txor {}, {}",
+                    Register::Rax.dword(),
+                    Register::Rax.dword()
+                )?;
+                writeln!(f, "\trep stosb")?;
+            }
+            StmtKind::Break => {
+                writeln!(f, "\tjmp {}", self.break_labels.last().unwrap())?;
+            }
+            StmtKind::Continue => {
+                writeln!(f, "\tjmp {}", self.continue_labels.last().unwrap())?;
+            }
+            StmtKind::Switch {
+                condition_expr,
+                run_stmt,
+                cases,
+                has_default,
+            } => {
+                self.generate_stmt_switch(f, condition_expr, *run_stmt, cases, has_default)?;
+            }
+            StmtKind::Case { index, stmt } => {
+                let label_num = self.switch_labels.last().unwrap();
+                writeln!(f, ".Lcase{}_{}:", label_num, index)?;
+                self.generate_stmt(f, *stmt)?;
+            }
+            StmtKind::Default { stmt } => {
+                let label_num = self.switch_labels.last().unwrap();
+                writeln!(f, ".Ldefault{}:", label_num)?;
+                self.generate_stmt(f, *stmt)?;
+            }
+            StmtKind::Goto { label } => {
+                writeln!(f, "\tjmp {}", self.user_label(&label))?;
+            }
+            StmtKind::Label { label, stmt } => {
+                writeln!(f, "{}:", self.user_label(&label))?;
+                self.generate_stmt(f, *stmt)?;
+            }
             StmtKind::Cpd { stmts } => {
                 self.generate_stmt_cpd(f, stmts)?;
             }
@@ -53,10 +111,18 @@ impl Generator {
         expr: Option<Expr>,
     ) -> anyhow::Result<()> {
         if let Some(expr) = expr {
+            let ty = expr.ty.clone();
             self.generate_expr(f, expr)?;
-            self.generate_pop(f, Register::Rax)?;
+            if ty.is_long_double() {
+                self.generate_pop_x87(f)?;
+            } else {
+                self.generate_pop(f, Register::Rax)?;
+                if ty.is_float() {
+                    self.generate_to_xmm(f, Register::Rax, 0)?;
+                }
+            }
         }
-        writeln!(f, "\tjmp .Lmain_ret")?;
+        writeln!(f, "\tjmp {}", self.return_label())?;
         Ok(())
     }
 
@@ -66,8 +132,9 @@ impl Generator {
         expr: Option<Expr>,
     ) -> anyhow::Result<()> {
         if let Some(expr) = expr {
+            let ty = expr.ty.clone();
             self.generate_expr(f, expr)?;
-            self.generate_pop(f, Register::Rax)?;
+            self.generate_discard(f, &ty)?;
         }
         Ok(())
     }
@@ -104,18 +171,26 @@ impl Generator {
     ) -> anyhow::Result<()> {
         let label_num = self.label_num();
         if let Some(init_expr) = init_expr {
+            let ty = init_expr.ty.clone();
             self.generate_expr(f, init_expr)?;
-            self.generate_pop(f, Register::Rax)?;
+            self.generate_discard(f, &ty)?;
         }
         writeln!(f, ".Lbegin{}:", label_num)?;
         self.generate_expr(f, condition_expr)?;
         self.generate_pop(f, Register::Rax)?;
         writeln!(f, "\tcmp {}, 0", Register::Rax.qword())?;
         writeln!(f, "\tje .Lend{}", label_num)?;
-        self.generate_stmt(f, run_stmt)?;
+        self.generate_loop_body(
+            f,
+            run_stmt,
+            format!(".Lend{}", label_num),
+            format!(".Lcontinue{}", label_num),
+        )?;
+        writeln!(f, ".Lcontinue{}:", label_num)?;
         if let Some(delta_expr) = delta_expr {
+            let ty = delta_expr.ty.clone();
             self.generate_expr(f, delta_expr)?;
-            self.generate_pop(f, Register::Rax)?;
+            self.generate_discard(f, &ty)?;
         }
         writeln!(f, "\tjmp .Lbegin{}", label_num)?;
         writeln!(f, ".Lend{}:", label_num)?;
@@ -134,12 +209,140 @@ impl Generator {
         self.generate_pop(f, Register::Rax)?;
         writeln!(f, "\tcmp {}, 0", Register::Rax.qword())?;
         writeln!(f, "\tje .Lend{}", label_num)?;
-        self.generate_stmt(f, run_stmt)?;
+        self.generate_loop_body(
+            f,
+            run_stmt,
+            format!(".Lend{}", label_num),
+            format!(".Lbegin{}", label_num),
+        )?;
         writeln!(f, "\tjmp .Lbegin{}", label_num)?;
         writeln!(f, ".Lend{}:", label_num)?;
         Ok(())
     }
 
+    fn generate_stmt_do_while(
+        &mut self,
+        f: &mut BufWriter<File>,
+        run_stmt: Stmt,
+        condition_expr: Expr,
+    ) -> anyhow::Result<()> {
+        let label_num = self.label_num();
+        writeln!(f, ".Lbegin{}:", label_num)?;
+        self.generate_loop_body(
+            f,
+            run_stmt,
+            format!(".Lend{}", label_num),
+            format!(".Lcontinue{}", label_num),
+        )?;
+        writeln!(f, ".Lcontinue{}:", label_num)?;
+        self.generate_expr(f, condition_expr)?;
+        self.generate_pop(f, Register::Rax)?;
+        writeln!(f, "\tcmp {}, 0", Register::Rax.qword())?;
+        writeln!(f, "\tjne .Lbegin{}", label_num)?;
+        writeln!(f, ".Lend{}:", label_num)?;
+        Ok(())
+    }
+
+    fn generate_stmt_switch(
+        &mut self,
+        f: &mut BufWriter<File>,
+        condition_expr: Expr,
+        run_stmt: Stmt,
+        cases: Vec<i64>,
+        has_default: bool,
+    ) -> anyhow::Result<()> {
+        let label_num = self.label_num();
+        let default_label = if has_default {
+            format!(".Ldefault{}", label_num)
+        } else {
+            format!(".Lend{}", label_num)
+        };
+        self.generate_expr(f, condition_expr)?;
+        self.generate_pop(f, Register::Rax)?;
+        let min = cases.iter().min().copied().unwrap_or(0);
+        let max = cases.iter().max().copied().unwrap_or(0);
+        let range = max.abs_diff(min).saturating_add(1);
+        // Dense cases jump through a table, sparse ones compare one by one.
+        if cases.len() >= 4 && range <= 3 * cases.len() as u64 {
+            writeln!(f, "\tmov {}, {}", Register::Rdi.qword(), min)?;
+            writeln!(
+                f,
+                "\tsub {}, {}",
+                Register::Rax.qword(),
+                Register::Rdi.qword()
+            )?;
+            writeln!(f, "\tcmp {}, {}", Register::Rax.qword(), range - 1)?;
+            writeln!(f, "\tja {}", default_label)?;
+            writeln!(
+                f,
+                "\tlea {}, [rip+.Ltable{}]",
+                Register::Rdi.qword(),
+                label_num
+            )?;
+            writeln!(
+                f,
+                "\tmovsxd {}, dword ptr [{}+{}*4]",
+                Register::Rax.qword(),
+                Register::Rdi.qword(),
+                Register::Rax.qword()
+            )?;
+            writeln!(
+                f,
+                "\tadd {}, {}",
+                Register::Rax.qword(),
+                Register::Rdi.qword()
+            )?;
+            writeln!(f, "\tjmp {}", Register::Rax.qword())?;
+            writeln!(f, ".section .rodata")?;
+            writeln!(f, "\t.align 4")?;
+            writeln!(f, ".Ltable{}:", label_num)?;
+            for offset in 0..range {
+                let value = min.wrapping_add(offset as i64);
+                let target = match cases.iter().position(|case| *case == value) {
+                    Some(index) => format!(".Lcase{}_{}", label_num, index),
+                    None => default_label.clone(),
+                };
+                writeln!(f, "\t.long {}-.Ltable{}", target, label_num)?;
+            }
+            writeln!(f, ".text")?;
+        } else {
+            for (index, value) in cases.iter().enumerate() {
+                writeln!(f, "\tmov {}, {}", Register::Rdi.qword(), value)?;
+                writeln!(
+                    f,
+                    "\tcmp {}, {}",
+                    Register::Rax.qword(),
+                    Register::Rdi.qword()
+                )?;
+                writeln!(f, "\tje .Lcase{}_{}", label_num, index)?;
+            }
+            writeln!(f, "\tjmp {}", default_label)?;
+        }
+        self.switch_labels.push(label_num);
+        self.break_labels.push(format!(".Lend{}", label_num));
+        self.generate_stmt(f, run_stmt)?;
+        self.break_labels.pop();
+        self.switch_labels.pop();
+        writeln!(f, ".Lend{}:", label_num)?;
+        Ok(())
+    }
+
+    /// Generates a loop body with the targets of `break` and `continue`.
+    fn generate_loop_body(
+        &mut self,
+        f: &mut BufWriter<File>,
+        run_stmt: Stmt,
+        break_label: String,
+        continue_label: String,
+    ) -> anyhow::Result<()> {
+        self.break_labels.push(break_label);
+        self.continue_labels.push(continue_label);
+        self.generate_stmt(f, run_stmt)?;
+        self.break_labels.pop();
+        self.continue_labels.pop();
+        Ok(())
+    }
+
     fn generate_stmt_cpd(
         &mut self,
         f: &mut BufWriter<File>,
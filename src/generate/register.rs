@@ -41,7 +41,6 @@ impl Register {
         }
     }
 
-    #[allow(dead_code)]
     pub fn dword(&self) -> &str {
         match self {
             Self::Rax => "eax",
@@ -63,7 +62,6 @@ impl Register {
         }
     }
 
-    #[allow(dead_code)]
     pub fn word(&self) -> &str {
         match self {
             Self::Rax => "ax",
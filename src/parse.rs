@@ -1,4 +1,6 @@
 pub mod parser;
+pub mod row_decl;
 pub mod row_expr;
+pub mod row_function;
 pub mod row_program;
 pub mod row_stmt;
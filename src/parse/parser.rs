@@ -1,25 +1,57 @@
+mod decl;
 mod expr;
 mod stmt;
 
-use std::collections::VecDeque;
+use std::collections::{BTreeMap, VecDeque};
 
 use crate::tokenize::token_stream::TokenStream;
 
-use super::row_program::RowProgram;
+use super::{row_decl::RowDeclarator, row_program::RowProgram};
 
-pub struct Parser {}
+pub struct Parser {
+    /// Ordinary identifiers of each open scope, mapped to whether they name a typedef.
+    scopes: Vec<BTreeMap<String, bool>>,
+}
 
 impl Parser {
     pub fn new() -> Self {
-        Self {}
+        Self {
+            scopes: vec![BTreeMap::new()],
+        }
     }
 
     pub fn parse(&mut self, token_stream: &mut TokenStream) -> anyhow::Result<RowProgram> {
-        let mut stmts = VecDeque::new();
+        let mut external_decls = VecDeque::new();
         while !token_stream.at_eof()? {
-            let stmt = self.parse_stmt(token_stream)?;
-            stmts.push_back(stmt);
+            let external_decl = self.parse_external_decl(token_stream)?;
+            external_decls.push_back(external_decl);
         }
-        Ok(RowProgram::new(stmts))
+        Ok(RowProgram::new(external_decls))
+    }
+
+    fn enter_scope(&mut self) {
+        self.scopes.push(BTreeMap::new());
+    }
+
+    fn leave_scope(&mut self) {
+        self.scopes.pop();
+    }
+
+    fn declare_name(&mut self, declarator: &RowDeclarator, is_typedef: bool) {
+        if let Some(name) = &declarator.name {
+            self.scopes
+                .last_mut()
+                .unwrap()
+                .insert(name.clone(), is_typedef);
+        }
+    }
+
+    fn is_typedef_name(&self, name: &str) -> bool {
+        self.scopes
+            .iter()
+            .rev()
+            .find_map(|scope| scope.get(name))
+            .copied()
+            .unwrap_or(false)
     }
 }
@@ -0,0 +1,536 @@
+use anyhow::anyhow;
+
+use crate::{
+    parse::{
+        row_decl::{
+            RowDecl, RowDeclSpec, RowDeclarator, RowDerived, RowDesignator, RowEnumerator,
+            RowInitDeclarator, RowInitItem, RowInitializer, RowMember, RowParam, RowStorageClass,
+            RowTypeName, RowTypeSpec,
+        },
+        row_function::RowFunction,
+        row_program::RowExternalDecl,
+    },
+    tokenize::{
+        token_kind::{PuncToken, TokenKind},
+        token_stream::TokenStream,
+    },
+};
+
+use super::Parser;
+
+impl Parser {
+    pub fn parse_external_decl(
+        &mut self,
+        token_stream: &mut TokenStream,
+    ) -> anyhow::Result<RowExternalDecl> {
+        let decl_spec = self.parse_decl_spec(token_stream)?;
+        if token_stream.consume(TokenKind::Punc(PuncToken::Semicolon))? {
+            token_stream.next()?;
+            let decl = RowDecl::new(decl_spec, Vec::new());
+            return Ok(RowExternalDecl::Decl { decl });
+        }
+        let declarator = self.parse_declarator(token_stream)?;
+        if matches!(declarator.derived.last(), Some(RowDerived::Function { .. }))
+            && token_stream.consume(TokenKind::Punc(PuncToken::OpenCurly))?
+        {
+            self.declare_name(&declarator, false);
+            self.enter_scope();
+            if let Some(RowDerived::Function { params, .. }) = declarator.derived.last() {
+                for param in params.iter() {
+                    self.declare_name(&param.declarator, false);
+                }
+            }
+            let body = self.parse_cpd_stmt(token_stream)?;
+            self.leave_scope();
+            let position = declarator.position.clone();
+            let function = Box::new(RowFunction::new(decl_spec, declarator, body, position));
+            return Ok(RowExternalDecl::Function { function });
+        }
+        let decl = self.parse_init_declarators(token_stream, decl_spec, declarator)?;
+        Ok(RowExternalDecl::Decl { decl })
+    }
+
+    pub fn parse_decl(&mut self, token_stream: &mut TokenStream) -> anyhow::Result<RowDecl> {
+        let decl_spec = self.parse_decl_spec(token_stream)?;
+        if token_stream.consume(TokenKind::Punc(PuncToken::Semicolon))? {
+            token_stream.next()?;
+            return Ok(RowDecl::new(decl_spec, Vec::new()));
+        }
+        let declarator = self.parse_declarator(token_stream)?;
+        self.parse_init_declarators(token_stream, decl_spec, declarator)
+    }
+
+    fn parse_init_declarators(
+        &mut self,
+        token_stream: &mut TokenStream,
+        decl_spec: RowDeclSpec,
+        declarator: RowDeclarator,
+    ) -> anyhow::Result<RowDecl> {
+        let mut init_declarators = Vec::new();
+        let mut declarator = declarator;
+        let is_typedef = matches!(decl_spec.storage_class, Some(RowStorageClass::Typedef));
+        loop {
+            self.declare_name(&declarator, is_typedef);
+            let init = if token_stream.consume(TokenKind::Punc(PuncToken::Equal))? {
+                token_stream.next()?;
+                Some(self.parse_initializer(token_stream)?)
+            } else {
+                None
+            };
+            init_declarators.push(RowInitDeclarator::new(declarator, init));
+            if !token_stream.consume(TokenKind::Punc(PuncToken::Comma))? {
+                break;
+            }
+            token_stream.next()?;
+            declarator = self.parse_declarator(token_stream)?;
+        }
+        token_stream.expect(TokenKind::Punc(PuncToken::Semicolon))?;
+        Ok(RowDecl::new(decl_spec, init_declarators))
+    }
+
+    fn parse_initializer(
+        &mut self,
+        token_stream: &mut TokenStream,
+    ) -> anyhow::Result<RowInitializer> {
+        if !token_stream.consume(TokenKind::Punc(PuncToken::OpenCurly))? {
+            let expr = self.parse_assignment_expr(token_stream)?;
+            return Ok(RowInitializer::Expr { expr });
+        }
+        let token = token_stream.next()?;
+        let mut items = Vec::new();
+        while !token_stream.consume(TokenKind::Punc(PuncToken::CloseCurly))? {
+            let mut designators = Vec::new();
+            loop {
+                let position = token_stream.get_position()?;
+                if token_stream.consume(TokenKind::Punc(PuncToken::OpenSquare))? {
+                    token_stream.next()?;
+                    let expr = self.parse_conditional_expr(token_stream)?;
+                    token_stream.expect(TokenKind::Punc(PuncToken::CloseSquare))?;
+                    designators.push(RowDesignator::Index { expr, position });
+                } else if token_stream.consume(TokenKind::Punc(PuncToken::Dot))? {
+                    token_stream.next()?;
+                    let name = self.parse_member_name(token_stream)?;
+                    designators.push(RowDesignator::Member { name, position });
+                } else {
+                    break;
+                }
+            }
+            if !designators.is_empty() {
+                token_stream.expect(TokenKind::Punc(PuncToken::Equal))?;
+            }
+            let init = self.parse_initializer(token_stream)?;
+            items.push(RowInitItem::new(designators, init));
+            if !token_stream.consume(TokenKind::Punc(PuncToken::Comma))? {
+                break;
+            }
+            token_stream.next()?;
+        }
+        token_stream.expect(TokenKind::Punc(PuncToken::CloseCurly))?;
+        Ok(RowInitializer::List {
+            items,
+            position: token.position,
+        })
+    }
+
+    pub fn is_decl_spec(&self, token_stream: &TokenStream) -> anyhow::Result<bool> {
+        Ok(self.is_decl_spec_kind(&token_stream.peek()?.kind))
+    }
+
+    pub fn is_decl_spec_kind(&self, kind: &TokenKind) -> bool {
+        if let TokenKind::Ident(name) = kind {
+            return self.is_typedef_name(name);
+        }
+        matches!(
+            kind,
+            TokenKind::Typedef
+                | TokenKind::Static
+                | TokenKind::Extern
+                | TokenKind::Auto
+                | TokenKind::Const
+                | TokenKind::Volatile
+                | TokenKind::Void
+                | TokenKind::Char
+                | TokenKind::Short
+                | TokenKind::Int
+                | TokenKind::Long
+                | TokenKind::Float
+                | TokenKind::Double
+                | TokenKind::Signed
+                | TokenKind::Unsigned
+                | TokenKind::Struct
+                | TokenKind::Union
+                | TokenKind::Enum
+        )
+    }
+
+    pub fn parse_type_name(
+        &mut self,
+        token_stream: &mut TokenStream,
+    ) -> anyhow::Result<RowTypeName> {
+        let position = token_stream.get_position()?;
+        let decl_spec = self.parse_decl_spec(token_stream)?;
+        if decl_spec.storage_class.is_some() {
+            return Err(anyhow!(format!(
+                "{}Invalid storage class in type name",
+                position
+            )));
+        }
+        let declarator = self.parse_declarator(token_stream)?;
+        if declarator.name.is_some() {
+            return Err(anyhow!(format!(
+                "{}Unexpected identifier in type name",
+                declarator.position
+            )));
+        }
+        Ok(RowTypeName::new(decl_spec, declarator))
+    }
+
+    fn parse_decl_spec(&mut self, token_stream: &mut TokenStream) -> anyhow::Result<RowDeclSpec> {
+        let position = token_stream.get_position()?;
+        let (mut void, mut char, mut short, mut int, mut long, mut signed, mut unsigned) =
+            (0, 0, 0, 0, 0, 0, 0);
+        let (mut float, mut double) = (0, 0);
+        let mut storage_class = None;
+        let mut is_const = false;
+        let mut named_spec = None;
+        while self.is_decl_spec(token_stream)? {
+            let token = token_stream.peek()?;
+            match *token.kind {
+                TokenKind::Const | TokenKind::Volatile => {
+                    token_stream.next()?;
+                    is_const |= *token.kind == TokenKind::Const;
+                    continue;
+                }
+                TokenKind::Typedef | TokenKind::Static | TokenKind::Extern | TokenKind::Auto => {
+                    if storage_class.is_some() {
+                        return Err(anyhow!(format!(
+                            "{}Multiple storage classes in declaration specifiers",
+                            token.position
+                        )));
+                    }
+                    token_stream.next()?;
+                    storage_class = Some(match *token.kind {
+                        TokenKind::Typedef => RowStorageClass::Typedef,
+                        TokenKind::Static => RowStorageClass::Static,
+                        TokenKind::Extern => RowStorageClass::Extern,
+                        _ => RowStorageClass::Auto,
+                    });
+                    continue;
+                }
+                TokenKind::Ident(name) => {
+                    // A typedef name after another type specifier is the declarator.
+                    if named_spec.is_some()
+                        || (void, char, short, int, long, signed, unsigned) != (0, 0, 0, 0, 0, 0, 0)
+                        || (float, double) != (0, 0)
+                    {
+                        break;
+                    }
+                    token_stream.next()?;
+                    named_spec = Some(RowTypeSpec::TypedefName {
+                        name,
+                        position: token.position,
+                    });
+                    continue;
+                }
+                TokenKind::Struct | TokenKind::Union | TokenKind::Enum => {
+                    if named_spec.is_some() {
+                        return Err(anyhow!(format!(
+                            "{}Invalid combination of type specifiers",
+                            token.position
+                        )));
+                    }
+                    named_spec = Some(if *token.kind == TokenKind::Enum {
+                        self.parse_enum_spec(token_stream)?
+                    } else {
+                        self.parse_struct_spec(token_stream)?
+                    });
+                    continue;
+                }
+                _ => {}
+            }
+            let token = token_stream.next()?;
+            match *token.kind {
+                TokenKind::Void => void += 1,
+                TokenKind::Char => char += 1,
+                TokenKind::Short => short += 1,
+                TokenKind::Int => int += 1,
+                TokenKind::Long => long += 1,
+                TokenKind::Float => float += 1,
+                TokenKind::Double => double += 1,
+                TokenKind::Signed => signed += 1,
+                TokenKind::Unsigned => unsigned += 1,
+                _ => unreachable!(),
+            }
+        }
+        let counts = (void, char, short, int, long, signed, unsigned);
+        if let Some(named_spec) = named_spec {
+            if counts != (0, 0, 0, 0, 0, 0, 0) || (float, double) != (0, 0) {
+                return Err(anyhow!(format!(
+                    "{}Invalid combination of type specifiers",
+                    position
+                )));
+            }
+            return Ok(RowDeclSpec::new(storage_class, is_const, named_spec));
+        }
+        if (float, double) != (0, 0) {
+            let type_spec = match (float, double, counts) {
+                (1, 0, (0, 0, 0, 0, 0, 0, 0)) => RowTypeSpec::Float,
+                (0, 1, (0, 0, 0, 0, 0, 0, 0)) => RowTypeSpec::Double,
+                (0, 1, (0, 0, 0, 0, 1, 0, 0)) => RowTypeSpec::LongDouble,
+                _ => {
+                    return Err(anyhow!(format!(
+                        "{}Invalid combination of type specifiers",
+                        position
+                    )));
+                }
+            };
+            return Ok(RowDeclSpec::new(storage_class, is_const, type_spec));
+        }
+        let type_spec = match counts {
+            (0, 0, 0, 0, 0, 0, 0) => {
+                return Err(anyhow!(format!("{}Expect type specifier", position)));
+            }
+            (1, 0, 0, 0, 0, 0, 0) => RowTypeSpec::Void,
+            (0, 1, 0, 0, 0, 0, 0) => RowTypeSpec::Char,
+            (0, 1, 0, 0, 0, 1, 0) => RowTypeSpec::SignedChar,
+            (0, 1, 0, 0, 0, 0, 1) => RowTypeSpec::UnsignedChar,
+            (0, 0, 1, 0 | 1, 0, 0 | 1, 0) => RowTypeSpec::Short,
+            (0, 0, 1, 0 | 1, 0, 0, 1) => RowTypeSpec::UnsignedShort,
+            (0, 0, 0, 1, 0, 0 | 1, 0) | (0, 0, 0, 0, 0, 1, 0) => RowTypeSpec::Int,
+            (0, 0, 0, 0 | 1, 0, 0, 1) => RowTypeSpec::UnsignedInt,
+            (0, 0, 0, 0 | 1, 1, 0 | 1, 0) => RowTypeSpec::Long,
+            (0, 0, 0, 0 | 1, 1, 0, 1) => RowTypeSpec::UnsignedLong,
+            (0, 0, 0, 0 | 1, 2, 0 | 1, 0) => RowTypeSpec::LongLong,
+            (0, 0, 0, 0 | 1, 2, 0, 1) => RowTypeSpec::UnsignedLongLong,
+            _ => {
+                return Err(anyhow!(format!(
+                    "{}Invalid combination of type specifiers",
+                    position
+                )));
+            }
+        };
+        Ok(RowDeclSpec::new(storage_class, is_const, type_spec))
+    }
+
+    fn parse_struct_spec(&mut self, token_stream: &mut TokenStream) -> anyhow::Result<RowTypeSpec> {
+        let token = token_stream.next()?;
+        let is_union = *token.kind == TokenKind::Union;
+        let tag = if let TokenKind::Ident(tag) = *token_stream.peek()?.kind {
+            token_stream.next()?;
+            Some(tag)
+        } else {
+            None
+        };
+        if !token_stream.consume(TokenKind::Punc(PuncToken::OpenCurly))? {
+            if tag.is_none() {
+                token_stream.expect(TokenKind::Punc(PuncToken::OpenCurly))?;
+            }
+            return Ok(RowTypeSpec::Struct {
+                is_union,
+                tag,
+                members: None,
+                position: token.position,
+            });
+        }
+        token_stream.next()?;
+        let mut members = Vec::new();
+        while !token_stream.consume(TokenKind::Punc(PuncToken::CloseCurly))? {
+            let position = token_stream.get_position()?;
+            let decl_spec = self.parse_decl_spec(token_stream)?;
+            if decl_spec.storage_class.is_some() {
+                return Err(anyhow!(format!(
+                    "{}Invalid storage class for member",
+                    position
+                )));
+            }
+            let mut declarators = Vec::new();
+            if !token_stream.consume(TokenKind::Punc(PuncToken::Semicolon))? {
+                declarators.push(self.parse_declarator(token_stream)?);
+                while token_stream.consume(TokenKind::Punc(PuncToken::Comma))? {
+                    token_stream.next()?;
+                    declarators.push(self.parse_declarator(token_stream)?);
+                }
+            }
+            token_stream.expect(TokenKind::Punc(PuncToken::Semicolon))?;
+            members.push(RowMember::new(decl_spec, declarators));
+        }
+        token_stream.next()?;
+        Ok(RowTypeSpec::Struct {
+            is_union,
+            tag,
+            members: Some(members),
+            position: token.position,
+        })
+    }
+
+    fn parse_enum_spec(&mut self, token_stream: &mut TokenStream) -> anyhow::Result<RowTypeSpec> {
+        let token = token_stream.expect(TokenKind::Enum)?;
+        let tag = if let TokenKind::Ident(tag) = *token_stream.peek()?.kind {
+            token_stream.next()?;
+            Some(tag)
+        } else {
+            None
+        };
+        if !token_stream.consume(TokenKind::Punc(PuncToken::OpenCurly))? {
+            if tag.is_none() {
+                token_stream.expect(TokenKind::Punc(PuncToken::OpenCurly))?;
+            }
+            return Ok(RowTypeSpec::Enum {
+                tag,
+                enumerators: None,
+                position: token.position,
+            });
+        }
+        token_stream.next()?;
+        let mut enumerators = Vec::new();
+        loop {
+            let token = token_stream.next()?;
+            let TokenKind::Ident(name) = *token.kind else {
+                return Err(anyhow!(format!(
+                    "{}Got unexpected token `{:?}`. Expect enumerator",
+                    token.position, token.kind
+                )));
+            };
+            let value = if token_stream.consume(TokenKind::Punc(PuncToken::Equal))? {
+                token_stream.next()?;
+                Some(self.parse_conditional_expr(token_stream)?)
+            } else {
+                None
+            };
+            self.scopes.last_mut().unwrap().insert(name.clone(), false);
+            enumerators.push(RowEnumerator::new(name, value, token.position));
+            if !token_stream.consume(TokenKind::Punc(PuncToken::Comma))? {
+                break;
+            }
+            token_stream.next()?;
+            if token_stream.consume(TokenKind::Punc(PuncToken::CloseCurly))? {
+                break;
+            }
+        }
+        token_stream.expect(TokenKind::Punc(PuncToken::CloseCurly))?;
+        Ok(RowTypeSpec::Enum {
+            tag,
+            enumerators: Some(enumerators),
+            position: token.position,
+        })
+    }
+
+    pub fn parse_declarator(
+        &mut self,
+        token_stream: &mut TokenStream,
+    ) -> anyhow::Result<RowDeclarator> {
+        let mut derived = Vec::new();
+        while token_stream.consume(TokenKind::Punc(PuncToken::Asterisk))? {
+            token_stream.next()?;
+            let mut is_const = false;
+            loop {
+                match *token_stream.peek()?.kind {
+                    TokenKind::Const => is_const = true,
+                    TokenKind::Volatile => {}
+                    _ => break,
+                }
+                token_stream.next()?;
+            }
+            derived.push(RowDerived::Pointer { is_const });
+        }
+        let position = token_stream.get_position()?;
+        let (name, inner_derived) = if self.is_nested_declarator(token_stream)? {
+            token_stream.next()?;
+            let inner = self.parse_declarator(token_stream)?;
+            token_stream.expect(TokenKind::Punc(PuncToken::CloseRound))?;
+            (inner.name, inner.derived)
+        } else if let TokenKind::Ident(name) = *token_stream.peek()?.kind {
+            token_stream.next()?;
+            (Some(name), Vec::new())
+        } else {
+            (None, Vec::new())
+        };
+        let mut suffixes = Vec::new();
+        loop {
+            if token_stream.consume(TokenKind::Punc(PuncToken::OpenRound))? {
+                suffixes.push(self.parse_func_params(token_stream)?);
+            } else if token_stream.consume(TokenKind::Punc(PuncToken::OpenSquare))? {
+                suffixes.push(self.parse_array_len(token_stream)?);
+            } else {
+                break;
+            }
+        }
+        derived.extend(suffixes.into_iter().rev());
+        derived.extend(inner_derived);
+        Ok(RowDeclarator::new(name, derived, position))
+    }
+
+    /// Distinguishes `(*name)` in a declarator from the parameter list of a function.
+    fn is_nested_declarator(&self, token_stream: &TokenStream) -> anyhow::Result<bool> {
+        if *token_stream.peek()?.kind != TokenKind::Punc(PuncToken::OpenRound) {
+            return Ok(false);
+        }
+        Ok(match *token_stream.peek_nth(1)?.kind {
+            TokenKind::Punc(PuncToken::Asterisk | PuncToken::OpenRound) => true,
+            TokenKind::Ident(name) => !self.is_typedef_name(&name),
+            _ => false,
+        })
+    }
+
+    fn parse_array_len(&mut self, token_stream: &mut TokenStream) -> anyhow::Result<RowDerived> {
+        token_stream.expect(TokenKind::Punc(PuncToken::OpenSquare))?;
+        if token_stream.consume(TokenKind::Punc(PuncToken::CloseSquare))? {
+            token_stream.next()?;
+            return Ok(RowDerived::Array { len: None });
+        }
+        let len = self.parse_conditional_expr(token_stream)?;
+        token_stream.expect(TokenKind::Punc(PuncToken::CloseSquare))?;
+        Ok(RowDerived::Array { len: Some(len) })
+    }
+
+    fn parse_func_params(&mut self, token_stream: &mut TokenStream) -> anyhow::Result<RowDerived> {
+        token_stream.expect(TokenKind::Punc(PuncToken::OpenRound))?;
+        let mut params = Vec::new();
+        if token_stream.consume(TokenKind::Punc(PuncToken::CloseRound))? {
+            token_stream.next()?;
+            return Ok(RowDerived::Function {
+                params,
+                variadic: true,
+            });
+        }
+        let mut tmp_token_stream = token_stream.clone();
+        if tmp_token_stream.consume(TokenKind::Void)? {
+            tmp_token_stream.next()?;
+            if tmp_token_stream.consume(TokenKind::Punc(PuncToken::CloseRound))? {
+                tmp_token_stream.next()?;
+                *token_stream = tmp_token_stream;
+                return Ok(RowDerived::Function {
+                    params,
+                    variadic: false,
+                });
+            }
+        }
+        let mut variadic = false;
+        self.enter_scope();
+        loop {
+            if token_stream.consume(TokenKind::Punc(PuncToken::DotDotDot))? {
+                token_stream.next()?;
+                variadic = true;
+                break;
+            }
+            let position = token_stream.get_position()?;
+            let decl_spec = self.parse_decl_spec(token_stream)?;
+            if decl_spec.storage_class.is_some() {
+                return Err(anyhow!(format!(
+                    "{}Invalid storage class for parameter",
+                    position
+                )));
+            }
+            let declarator = self.parse_declarator(token_stream)?;
+            self.declare_name(&declarator, false);
+            params.push(RowParam::new(decl_spec, declarator));
+            if !token_stream.consume(TokenKind::Punc(PuncToken::Comma))? {
+                break;
+            }
+            token_stream.next()?;
+        }
+        self.leave_scope();
+        token_stream.expect(TokenKind::Punc(PuncToken::CloseRound))?;
+        Ok(RowDerived::Function { params, variadic })
+    }
+}
@@ -1,7 +1,7 @@
 use anyhow::anyhow;
 
 use crate::{
-    parse::row_expr::{RowAssignOpKind, RowBinaryOpKind, RowExpr},
+    parse::row_expr::{RowAssignOpKind, RowBinaryOpKind, RowExpr, RowUnaryOpKind},
     tokenize::{
         token::Token,
         token_kind::{PuncToken, TokenKind},
@@ -22,10 +22,12 @@ impl Parser {
         Ok(expr)
     }
 
-    fn parse_assignment_expr(&mut self, token_stream: &mut TokenStream) -> anyhow::Result<RowExpr> {
-        let mut tmp_token_stream = token_stream.clone();
-        let lhs = self.parse_unary_expr(&mut tmp_token_stream)?;
-        let token = tmp_token_stream.peek()?;
+    pub fn parse_assignment_expr(
+        &mut self,
+        token_stream: &mut TokenStream,
+    ) -> anyhow::Result<RowExpr> {
+        let lhs = self.parse_conditional_expr(token_stream)?;
+        let token = token_stream.peek()?;
         let op_kind = match *token.kind {
             TokenKind::Punc(punc) => match punc {
                 PuncToken::Equal => RowAssignOpKind::Equal,
@@ -39,17 +41,16 @@ impl Parser {
                 PuncToken::AndEqual => RowAssignOpKind::BitAndEqual,
                 PuncToken::HatEqual => RowAssignOpKind::BitXorEqual,
                 PuncToken::VertEqual => RowAssignOpKind::BitOrEqual,
-                _ => return self.parse_conditional_expr(token_stream),
+                _ => return Ok(lhs),
             },
-            _ => return self.parse_conditional_expr(token_stream),
+            _ => return Ok(lhs),
         };
-        tmp_token_stream.next()?;
-        let rhs = self.parse_assignment_expr(&mut tmp_token_stream)?;
-        *token_stream = tmp_token_stream;
+        token_stream.next()?;
+        let rhs = self.parse_assignment_expr(token_stream)?;
         Ok(RowExpr::new_assign(op_kind, lhs, rhs, token.position))
     }
 
-    fn parse_conditional_expr(
+    pub fn parse_conditional_expr(
         &mut self,
         token_stream: &mut TokenStream,
     ) -> anyhow::Result<RowExpr> {
@@ -230,25 +231,65 @@ impl Parser {
     }
 
     fn parse_cast_expr(&mut self, token_stream: &mut TokenStream) -> anyhow::Result<RowExpr> {
+        if token_stream.consume(TokenKind::Punc(PuncToken::OpenRound))?
+            && self.is_decl_spec_kind(&token_stream.peek_nth(1)?.kind)
+        {
+            let token = token_stream.next()?;
+            let type_name = self.parse_type_name(token_stream)?;
+            token_stream.expect(TokenKind::Punc(PuncToken::CloseRound))?;
+            let expr = self.parse_cast_expr(token_stream)?;
+            return Ok(RowExpr::new_cast(type_name, expr, token.position));
+        }
         self.parse_unary_expr(token_stream)
     }
 
     fn parse_unary_expr(&mut self, token_stream: &mut TokenStream) -> anyhow::Result<RowExpr> {
         let Token { kind, position } = token_stream.peek()?;
-        Ok(match *kind {
+        let op_kind = match *kind {
             TokenKind::Punc(punc) => match punc {
                 PuncToken::PlusPlus => {
                     token_stream.next()?;
-                    RowExpr::new_unary_increment(self.parse_postfix_expr(token_stream)?, position)
+                    let expr = self.parse_unary_expr(token_stream)?;
+                    return Ok(RowExpr::new_unary_increment(expr, position));
                 }
                 PuncToken::MinusMinus => {
                     token_stream.next()?;
-                    RowExpr::new_unary_decrement(self.parse_postfix_expr(token_stream)?, position)
+                    let expr = self.parse_unary_expr(token_stream)?;
+                    return Ok(RowExpr::new_unary_decrement(expr, position));
                 }
-                _ => self.parse_postfix_expr(token_stream)?,
+                PuncToken::And => RowUnaryOpKind::Addr,
+                PuncToken::Asterisk => RowUnaryOpKind::Deref,
+                PuncToken::Plus => RowUnaryOpKind::Plus,
+                PuncToken::Minus => RowUnaryOpKind::Minus,
+                PuncToken::Tilde => RowUnaryOpKind::BitNot,
+                PuncToken::Excl => RowUnaryOpKind::LogicNot,
+                _ => return self.parse_postfix_expr(token_stream),
             },
-            _ => self.parse_postfix_expr(token_stream)?,
-        })
+            TokenKind::Sizeof => {
+                token_stream.next()?;
+                if token_stream.consume(TokenKind::Punc(PuncToken::OpenRound))?
+                    && self.is_decl_spec_kind(&token_stream.peek_nth(1)?.kind)
+                {
+                    token_stream.next()?;
+                    let type_name = self.parse_type_name(token_stream)?;
+                    token_stream.expect(TokenKind::Punc(PuncToken::CloseRound))?;
+                    return Ok(RowExpr::new_sizeof_type(type_name, position));
+                }
+                let expr = self.parse_unary_expr(token_stream)?;
+                return Ok(RowExpr::new_sizeof_expr(expr, position));
+            }
+            TokenKind::Alignof => {
+                token_stream.next()?;
+                token_stream.expect(TokenKind::Punc(PuncToken::OpenRound))?;
+                let type_name = self.parse_type_name(token_stream)?;
+                token_stream.expect(TokenKind::Punc(PuncToken::CloseRound))?;
+                return Ok(RowExpr::new_alignof_type(type_name, position));
+            }
+            _ => return self.parse_postfix_expr(token_stream),
+        };
+        token_stream.next()?;
+        let expr = self.parse_cast_expr(token_stream)?;
+        Ok(RowExpr::new_unary(op_kind, expr, position))
     }
 
     fn parse_postfix_expr(&mut self, token_stream: &mut TokenStream) -> anyhow::Result<RowExpr> {
@@ -265,20 +306,42 @@ impl Parser {
                         token_stream.next()?;
                         expr = RowExpr::new_postfix_decrement(expr, position);
                     }
+                    PuncToken::OpenSquare => {
+                        token_stream.next()?;
+                        let index = self.parse_expr(token_stream)?;
+                        token_stream.expect(TokenKind::Punc(PuncToken::CloseSquare))?;
+                        let sum = RowExpr::new_binary(
+                            RowBinaryOpKind::Add,
+                            expr,
+                            index,
+                            position.clone(),
+                        );
+                        expr = RowExpr::new_unary(RowUnaryOpKind::Deref, sum, position);
+                    }
                     PuncToken::OpenRound => {
                         token_stream.next()?;
                         let mut args = Vec::new();
-                        if token_stream.consume(TokenKind::Punc(PuncToken::CloseRound))? {
-                            token_stream.next()?;
-                            return Ok(RowExpr::new_func(expr, args, position));
-                        }
-                        args.push(self.parse_assignment_expr(token_stream)?);
-                        while !token_stream.consume(TokenKind::Punc(PuncToken::CloseRound))? {
-                            token_stream.expect(TokenKind::Punc(PuncToken::Comma))?;
+                        if !token_stream.consume(TokenKind::Punc(PuncToken::CloseRound))? {
                             args.push(self.parse_assignment_expr(token_stream)?);
+                            while !token_stream.consume(TokenKind::Punc(PuncToken::CloseRound))? {
+                                token_stream.expect(TokenKind::Punc(PuncToken::Comma))?;
+                                args.push(self.parse_assignment_expr(token_stream)?);
+                            }
                         }
                         token_stream.next()?;
-                        return Ok(RowExpr::new_func(expr, args, position));
+                        expr = RowExpr::new_func(expr, args, position);
+                    }
+                    PuncToken::Dot => {
+                        token_stream.next()?;
+                        let member = self.parse_member_name(token_stream)?;
+                        expr = RowExpr::new_member(expr, member, position);
+                    }
+                    PuncToken::MinusGt => {
+                        token_stream.next()?;
+                        let member = self.parse_member_name(token_stream)?;
+                        let deref =
+                            RowExpr::new_unary(RowUnaryOpKind::Deref, expr, position.clone());
+                        expr = RowExpr::new_member(deref, member, position);
                     }
                     _ => break,
                 },
@@ -288,10 +351,31 @@ impl Parser {
         Ok(expr)
     }
 
+    pub fn parse_member_name(&mut self, token_stream: &mut TokenStream) -> anyhow::Result<String> {
+        let token = token_stream.next()?;
+        match *token.kind {
+            TokenKind::Ident(member) => Ok(member),
+            _ => Err(anyhow!(
+                "{}Got unexpected token `{:?}`. Expect member name",
+                token.position,
+                token.kind
+            )),
+        }
+    }
+
     fn parse_primary_expr(&mut self, token_stream: &mut TokenStream) -> anyhow::Result<RowExpr> {
         let token = token_stream.next()?;
         let expr = match *token.kind {
-            TokenKind::Number(number) => RowExpr::new_number(number, token.position),
+            TokenKind::Number(number, format) => {
+                RowExpr::new_number(number, format, token.position)
+            }
+            TokenKind::FloatNumber(literal, suffix) => {
+                RowExpr::new_float_number(literal, suffix, token.position)
+            }
+            TokenKind::Character(encoding, value) => {
+                RowExpr::new_character(encoding, value, token.position)
+            }
+            TokenKind::Str(encoding, units) => RowExpr::new_str(encoding, units, token.position),
             TokenKind::Ident(name) => RowExpr::new_ident(name, token.position),
             TokenKind::Punc(PuncToken::OpenRound) => {
                 let expr = self.parse_expr(token_stream)?;
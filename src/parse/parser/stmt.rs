@@ -1,5 +1,8 @@
+use anyhow::anyhow;
+
 use crate::{
-    parse::row_stmt::RowStmt,
+    file::position::Position,
+    parse::{row_expr::RowExpr, row_stmt::RowStmt},
     tokenize::{
         token_kind::{PuncToken, TokenKind},
         token_stream::TokenStream,
@@ -18,8 +21,46 @@ impl Parser {
             self.parse_for_stmt(token_stream)?
         } else if token_stream.consume(TokenKind::While)? {
             self.parse_while_stmt(token_stream)?
+        } else if token_stream.consume(TokenKind::Do)? {
+            self.parse_do_while_stmt(token_stream)?
+        } else if token_stream.consume(TokenKind::Break)? {
+            let token = token_stream.next()?;
+            token_stream.expect(TokenKind::Punc(PuncToken::Semicolon))?;
+            RowStmt::new_break(token.position)
+        } else if token_stream.consume(TokenKind::Continue)? {
+            let token = token_stream.next()?;
+            token_stream.expect(TokenKind::Punc(PuncToken::Semicolon))?;
+            RowStmt::new_continue(token.position)
+        } else if token_stream.consume(TokenKind::Switch)? {
+            self.parse_switch_stmt(token_stream)?
+        } else if token_stream.consume(TokenKind::Case)? {
+            let token = token_stream.next()?;
+            let expr = self.parse_conditional_expr(token_stream)?;
+            token_stream.expect(TokenKind::Punc(PuncToken::Colon))?;
+            let stmt = self.parse_stmt(token_stream)?;
+            RowStmt::new_case(expr, stmt, token.position)
+        } else if token_stream.consume(TokenKind::Default)? {
+            let token = token_stream.next()?;
+            token_stream.expect(TokenKind::Punc(PuncToken::Colon))?;
+            let stmt = self.parse_stmt(token_stream)?;
+            RowStmt::new_default(stmt, token.position)
+        } else if token_stream.consume(TokenKind::Goto)? {
+            let token = token_stream.next()?;
+            let label = self.parse_label_name(token_stream)?;
+            token_stream.expect(TokenKind::Punc(PuncToken::Semicolon))?;
+            RowStmt::new_goto(label, token.position)
+        } else if matches!(*token_stream.peek()?.kind, TokenKind::Ident(_))
+            && *token_stream.peek_nth(1)?.kind == TokenKind::Punc(PuncToken::Colon)
+        {
+            let position = token_stream.get_position()?;
+            let label = self.parse_label_name(token_stream)?;
+            token_stream.expect(TokenKind::Punc(PuncToken::Colon))?;
+            let stmt = self.parse_stmt(token_stream)?;
+            RowStmt::new_label(label, stmt, position)
         } else if token_stream.consume(TokenKind::Punc(PuncToken::OpenCurly))? {
             self.parse_cpd_stmt(token_stream)?
+        } else if self.is_decl_spec(token_stream)? {
+            self.parse_decl_stmt(token_stream)?
         } else {
             self.parse_expr_stmt(token_stream)?
         };
@@ -61,6 +102,14 @@ impl Parser {
     fn parse_for_stmt(&mut self, token_stream: &mut TokenStream) -> anyhow::Result<RowStmt> {
         let token = token_stream.expect(TokenKind::For)?;
         token_stream.expect(TokenKind::Punc(PuncToken::OpenRound))?;
+        // `for (decl; ...) stmt` is parsed as `{ decl; for (; ...) stmt }`.
+        if self.is_decl_spec(token_stream)? {
+            self.enter_scope();
+            let decl = self.parse_decl_stmt(token_stream)?;
+            let stmt = self.parse_for_stmt_rest(token_stream, None, token.position.clone());
+            self.leave_scope();
+            return Ok(RowStmt::new_cpd(vec![decl, stmt?], token.position));
+        }
         let init = if token_stream.consume(TokenKind::Punc(PuncToken::Semicolon))? {
             None
         } else {
@@ -68,6 +117,15 @@ impl Parser {
             Some(condition)
         };
         token_stream.expect(TokenKind::Punc(PuncToken::Semicolon))?;
+        self.parse_for_stmt_rest(token_stream, init, token.position)
+    }
+
+    fn parse_for_stmt_rest(
+        &mut self,
+        token_stream: &mut TokenStream,
+        init: Option<RowExpr>,
+        position: Position,
+    ) -> anyhow::Result<RowStmt> {
         let condition = if token_stream.consume(TokenKind::Punc(PuncToken::Semicolon))? {
             None
         } else {
@@ -83,13 +141,7 @@ impl Parser {
         };
         token_stream.expect(TokenKind::Punc(PuncToken::CloseRound))?;
         let run_stmt = self.parse_stmt(token_stream)?;
-        Ok(RowStmt::new_for(
-            init,
-            condition,
-            delta,
-            run_stmt,
-            token.position,
-        ))
+        Ok(RowStmt::new_for(init, condition, delta, run_stmt, position))
     }
 
     fn parse_while_stmt(&mut self, token_stream: &mut TokenStream) -> anyhow::Result<RowStmt> {
@@ -101,19 +153,60 @@ impl Parser {
         Ok(RowStmt::new_while(condition, run_stmt, token.position))
     }
 
-    fn parse_cpd_stmt(&mut self, token_stream: &mut TokenStream) -> anyhow::Result<RowStmt> {
+    fn parse_do_while_stmt(&mut self, token_stream: &mut TokenStream) -> anyhow::Result<RowStmt> {
+        let token = token_stream.expect(TokenKind::Do)?;
+        let run_stmt = self.parse_stmt(token_stream)?;
+        token_stream.expect(TokenKind::While)?;
+        token_stream.expect(TokenKind::Punc(PuncToken::OpenRound))?;
+        let condition = self.parse_expr(token_stream)?;
+        token_stream.expect(TokenKind::Punc(PuncToken::CloseRound))?;
+        token_stream.expect(TokenKind::Punc(PuncToken::Semicolon))?;
+        Ok(RowStmt::new_do_while(run_stmt, condition, token.position))
+    }
+
+    fn parse_switch_stmt(&mut self, token_stream: &mut TokenStream) -> anyhow::Result<RowStmt> {
+        let token = token_stream.expect(TokenKind::Switch)?;
+        token_stream.expect(TokenKind::Punc(PuncToken::OpenRound))?;
+        let condition = self.parse_expr(token_stream)?;
+        token_stream.expect(TokenKind::Punc(PuncToken::CloseRound))?;
+        let run_stmt = self.parse_stmt(token_stream)?;
+        Ok(RowStmt::new_switch(condition, run_stmt, token.position))
+    }
+
+    fn parse_label_name(&mut self, token_stream: &mut TokenStream) -> anyhow::Result<String> {
+        let token = token_stream.next()?;
+        match *token.kind {
+            TokenKind::Ident(label) => Ok(label),
+            _ => Err(anyhow!(
+                "{}Got unexpected token `{:?}`. Expect label",
+                token.position,
+                token.kind
+            )),
+        }
+    }
+
+    pub fn parse_cpd_stmt(&mut self, token_stream: &mut TokenStream) -> anyhow::Result<RowStmt> {
         let token = token_stream.expect(TokenKind::Punc(PuncToken::OpenCurly))?;
         let mut stmts = vec![];
+        self.enter_scope();
         while !token_stream.consume(TokenKind::Punc(PuncToken::CloseCurly))? {
             stmts.push(self.parse_stmt(token_stream)?);
         }
+        self.leave_scope();
         token_stream.next()?;
         Ok(RowStmt::new_cpd(stmts, token.position))
     }
 
+    fn parse_decl_stmt(&mut self, token_stream: &mut TokenStream) -> anyhow::Result<RowStmt> {
+        let position = token_stream.get_position()?;
+        let decl = self.parse_decl(token_stream)?;
+        Ok(RowStmt::new_decl(decl, position))
+    }
+
     fn parse_expr_stmt(&mut self, token_stream: &mut TokenStream) -> anyhow::Result<RowStmt> {
         let token = token_stream.peek()?;
         if *token.kind == TokenKind::Punc(PuncToken::Semicolon) {
+            token_stream.next()?;
             return Ok(RowStmt::new_expr(None, token.position));
         }
         let expr = self.parse_expr(token_stream)?;
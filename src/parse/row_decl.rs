@@ -0,0 +1,246 @@
+use crate::file::position::Position;
+
+use super::row_expr::RowExpr;
+
+#[derive(Debug, Clone)]
+pub struct RowDecl {
+    pub decl_spec: RowDeclSpec,
+    pub init_declarators: Vec<RowInitDeclarator>,
+}
+
+impl RowDecl {
+    pub fn new(decl_spec: RowDeclSpec, init_declarators: Vec<RowInitDeclarator>) -> Self {
+        Self {
+            decl_spec,
+            init_declarators,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RowInitDeclarator {
+    pub declarator: RowDeclarator,
+    pub init: Option<RowInitializer>,
+}
+
+impl RowInitDeclarator {
+    pub fn new(declarator: RowDeclarator, init: Option<RowInitializer>) -> Self {
+        Self { declarator, init }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum RowInitializer {
+    /// assignment-expression
+    Expr { expr: RowExpr },
+    /// '{' initializer-list '}'
+    List {
+        items: Vec<RowInitItem>,
+        position: Position,
+    },
+}
+
+impl RowInitializer {
+    pub fn position(&self) -> &Position {
+        match self {
+            Self::Expr { expr } => &expr.position,
+            Self::List { position, .. } => position,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RowInitItem {
+    pub designators: Vec<RowDesignator>,
+    pub init: RowInitializer,
+}
+
+impl RowInitItem {
+    pub fn new(designators: Vec<RowDesignator>, init: RowInitializer) -> Self {
+        Self { designators, init }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum RowDesignator {
+    /// '[' constant-expression ']'
+    Index { expr: RowExpr, position: Position },
+    /// '.' identifier
+    Member { name: String, position: Position },
+}
+
+#[derive(Debug, Clone)]
+pub struct RowDeclSpec {
+    pub storage_class: Option<RowStorageClass>,
+    pub is_const: bool,
+    pub type_spec: RowTypeSpec,
+}
+
+impl RowDeclSpec {
+    pub fn new(
+        storage_class: Option<RowStorageClass>,
+        is_const: bool,
+        type_spec: RowTypeSpec,
+    ) -> Self {
+        Self {
+            storage_class,
+            is_const,
+            type_spec,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum RowStorageClass {
+    /// 'typedef'
+    Typedef,
+    /// 'static'
+    Static,
+    /// 'extern'
+    Extern,
+    /// 'auto'
+    Auto,
+}
+
+#[derive(Debug, Clone)]
+pub enum RowTypeSpec {
+    /// 'void'
+    Void,
+    /// 'char'
+    Char,
+    /// 'signed char'
+    SignedChar,
+    /// 'unsigned char'
+    UnsignedChar,
+    /// 'short', 'signed short', 'short int', 'signed short int'
+    Short,
+    /// 'unsigned short', 'unsigned short int'
+    UnsignedShort,
+    /// 'int', 'signed', 'signed int'
+    Int,
+    /// 'unsigned', 'unsigned int'
+    UnsignedInt,
+    /// 'long', 'signed long', 'long int', 'signed long int'
+    Long,
+    /// 'unsigned long', 'unsigned long int'
+    UnsignedLong,
+    /// 'long long', 'signed long long', 'long long int', 'signed long long int'
+    LongLong,
+    /// 'unsigned long long', 'unsigned long long int'
+    UnsignedLongLong,
+    /// 'float'
+    Float,
+    /// 'double'
+    Double,
+    /// 'long double'
+    LongDouble,
+    /// 'struct' or 'union' specifier
+    Struct {
+        is_union: bool,
+        tag: Option<String>,
+        members: Option<Vec<RowMember>>,
+        position: Position,
+    },
+    /// typedef name
+    TypedefName { name: String, position: Position },
+    /// 'enum' specifier
+    Enum {
+        tag: Option<String>,
+        enumerators: Option<Vec<RowEnumerator>>,
+        position: Position,
+    },
+}
+
+#[derive(Debug, Clone)]
+pub struct RowEnumerator {
+    pub name: String,
+    pub value: Option<RowExpr>,
+    pub position: Position,
+}
+
+impl RowEnumerator {
+    pub fn new(name: String, value: Option<RowExpr>, position: Position) -> Self {
+        Self {
+            name,
+            value,
+            position,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RowMember {
+    pub decl_spec: RowDeclSpec,
+    pub declarators: Vec<RowDeclarator>,
+}
+
+impl RowMember {
+    pub fn new(decl_spec: RowDeclSpec, declarators: Vec<RowDeclarator>) -> Self {
+        Self {
+            decl_spec,
+            declarators,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RowDeclarator {
+    pub name: Option<String>,
+    pub derived: Vec<RowDerived>,
+    pub position: Position,
+}
+
+impl RowDeclarator {
+    pub fn new(name: Option<String>, derived: Vec<RowDerived>, position: Position) -> Self {
+        Self {
+            name,
+            derived,
+            position,
+        }
+    }
+}
+
+/// Type derivations of a declarator, applied in order from the declaration specifiers outward.
+#[derive(Debug, Clone)]
+pub enum RowDerived {
+    Pointer {
+        is_const: bool,
+    },
+    Array {
+        len: Option<RowExpr>,
+    },
+    Function {
+        params: Vec<RowParam>,
+        variadic: bool,
+    },
+}
+
+#[derive(Debug, Clone)]
+pub struct RowParam {
+    pub decl_spec: RowDeclSpec,
+    pub declarator: RowDeclarator,
+}
+
+impl RowParam {
+    pub fn new(decl_spec: RowDeclSpec, declarator: RowDeclarator) -> Self {
+        Self {
+            decl_spec,
+            declarator,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RowTypeName {
+    pub decl_spec: RowDeclSpec,
+    pub declarator: RowDeclarator,
+}
+
+impl RowTypeName {
+    pub fn new(decl_spec: RowDeclSpec, declarator: RowDeclarator) -> Self {
+        Self {
+            decl_spec,
+            declarator,
+        }
+    }
+}
@@ -1,4 +1,9 @@
-use crate::file::position::Position;
+use crate::{
+    file::position::Position,
+    tokenize::token_kind::{Encoding, FloatSuffix, IntegerFormat},
+};
+
+use super::row_decl::RowTypeName;
 
 #[derive(Debug, Clone)]
 pub struct RowExpr {
@@ -39,6 +44,57 @@ impl RowExpr {
         }
     }
 
+    pub fn new_unary(
+        row_unary_op_kind: RowUnaryOpKind,
+        row_expr: RowExpr,
+        position: Position,
+    ) -> Self {
+        Self {
+            kind: RowExprKind::Unary {
+                row_unary_op_kind,
+                row_expr: Box::new(row_expr),
+            },
+            position,
+        }
+    }
+
+    pub fn new_cast(row_type_name: RowTypeName, row_expr: RowExpr, position: Position) -> Self {
+        Self {
+            kind: RowExprKind::Cast {
+                row_type_name: Box::new(row_type_name),
+                row_expr: Box::new(row_expr),
+            },
+            position,
+        }
+    }
+
+    pub fn new_sizeof_expr(row_expr: RowExpr, position: Position) -> Self {
+        Self {
+            kind: RowExprKind::SizeofExpr {
+                row_expr: Box::new(row_expr),
+            },
+            position,
+        }
+    }
+
+    pub fn new_sizeof_type(row_type_name: RowTypeName, position: Position) -> Self {
+        Self {
+            kind: RowExprKind::SizeofType {
+                row_type_name: Box::new(row_type_name),
+            },
+            position,
+        }
+    }
+
+    pub fn new_alignof_type(row_type_name: RowTypeName, position: Position) -> Self {
+        Self {
+            kind: RowExprKind::AlignofType {
+                row_type_name: Box::new(row_type_name),
+            },
+            position,
+        }
+    }
+
     pub fn new_unary_increment(row_expr: RowExpr, position: Position) -> Self {
         Self {
             kind: RowExprKind::UnaryIncrement {
@@ -108,9 +164,40 @@ impl RowExpr {
         }
     }
 
-    pub fn new_number(number: usize, position: Position) -> Self {
+    pub fn new_number(number: usize, format: IntegerFormat, position: Position) -> Self {
+        Self {
+            kind: RowExprKind::Number { number, format },
+            position,
+        }
+    }
+
+    pub fn new_float_number(literal: String, suffix: FloatSuffix, position: Position) -> Self {
+        Self {
+            kind: RowExprKind::FloatNumber { literal, suffix },
+            position,
+        }
+    }
+
+    pub fn new_character(encoding: Encoding, value: i64, position: Position) -> Self {
         Self {
-            kind: RowExprKind::Number { number },
+            kind: RowExprKind::Character { encoding, value },
+            position,
+        }
+    }
+
+    pub fn new_str(encoding: Encoding, units: Vec<u32>, position: Position) -> Self {
+        Self {
+            kind: RowExprKind::Str { encoding, units },
+            position,
+        }
+    }
+
+    pub fn new_member(row_expr: RowExpr, member: String, position: Position) -> Self {
+        Self {
+            kind: RowExprKind::Member {
+                row_expr: Box::new(row_expr),
+                member,
+            },
             position,
         }
     }
@@ -151,6 +238,23 @@ pub enum RowExprKind {
         row_then_expr: Box<RowExpr>,
         row_else_expr: Box<RowExpr>,
     },
+    Unary {
+        row_unary_op_kind: RowUnaryOpKind,
+        row_expr: Box<RowExpr>,
+    },
+    Cast {
+        row_type_name: Box<RowTypeName>,
+        row_expr: Box<RowExpr>,
+    },
+    SizeofExpr {
+        row_expr: Box<RowExpr>,
+    },
+    SizeofType {
+        row_type_name: Box<RowTypeName>,
+    },
+    AlignofType {
+        row_type_name: Box<RowTypeName>,
+    },
     UnaryIncrement {
         row_expr: Box<RowExpr>,
     },
@@ -168,11 +272,30 @@ pub enum RowExprKind {
     },
     Number {
         number: usize,
+        format: IntegerFormat,
+    },
+    /// A floating constant as written, without its suffix.
+    FloatNumber {
+        literal: String,
+        suffix: FloatSuffix,
+    },
+    Character {
+        encoding: Encoding,
+        value: i64,
+    },
+    /// A string literal without its terminating null character.
+    Str {
+        encoding: Encoding,
+        units: Vec<u32>,
     },
     Func {
         row_name_expr: Box<RowExpr>,
         row_args_expr: Vec<RowExpr>,
     },
+    Member {
+        row_expr: Box<RowExpr>,
+        member: String,
+    },
 }
 
 #[derive(Debug, Clone)]
@@ -215,6 +338,22 @@ pub enum RowBinaryOpKind {
     NotEqual,
 }
 
+#[derive(Debug, Clone)]
+pub enum RowUnaryOpKind {
+    /// address operator ('&')
+    Addr,
+    /// indirection operator ('*')
+    Deref,
+    /// unary plus operator ('+')
+    Plus,
+    /// unary minus operator ('-')
+    Minus,
+    /// bitwise complement operator ('~')
+    BitNot,
+    /// logical negation operator ('!')
+    LogicNot,
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum RowAssignOpKind {
     /// simple assignment operator ('=')
@@ -0,0 +1,30 @@
+use crate::file::position::Position;
+
+use super::{
+    row_decl::{RowDeclSpec, RowDeclarator},
+    row_stmt::RowStmt,
+};
+
+#[derive(Debug, Clone)]
+pub struct RowFunction {
+    pub decl_spec: RowDeclSpec,
+    pub declarator: RowDeclarator,
+    pub body: RowStmt,
+    pub position: Position,
+}
+
+impl RowFunction {
+    pub fn new(
+        decl_spec: RowDeclSpec,
+        declarator: RowDeclarator,
+        body: RowStmt,
+        position: Position,
+    ) -> Self {
+        Self {
+            decl_spec,
+            declarator,
+            body,
+            position,
+        }
+    }
+}
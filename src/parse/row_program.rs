@@ -1,14 +1,20 @@
 use std::collections::VecDeque;
 
-use super::row_stmt::RowStmt;
+use super::{row_decl::RowDecl, row_function::RowFunction};
 
 #[derive(Debug)]
 pub struct RowProgram {
-    pub stmts: VecDeque<RowStmt>,
+    pub external_decls: VecDeque<RowExternalDecl>,
 }
 
 impl RowProgram {
-    pub fn new(stmts: VecDeque<RowStmt>) -> Self {
-        Self { stmts }
+    pub fn new(external_decls: VecDeque<RowExternalDecl>) -> Self {
+        Self { external_decls }
     }
 }
+
+#[derive(Debug)]
+pub enum RowExternalDecl {
+    Function { function: Box<RowFunction> },
+    Decl { decl: RowDecl },
+}
@@ -1,6 +1,6 @@
 use crate::file::position::Position;
 
-use super::row_expr::RowExpr;
+use super::{row_decl::RowDecl, row_expr::RowExpr};
 
 #[derive(Debug, Clone)]
 pub struct RowStmt {
@@ -67,12 +67,89 @@ impl RowStmt {
         }
     }
 
+    pub fn new_do_while(run_stmt: RowStmt, condition_expr: RowExpr, position: Position) -> Self {
+        Self {
+            kind: RowStmtKind::DoWhile {
+                run_stmt: Box::new(run_stmt),
+                condition_expr,
+            },
+            position,
+        }
+    }
+
+    pub fn new_break(position: Position) -> Self {
+        Self {
+            kind: RowStmtKind::Break,
+            position,
+        }
+    }
+
+    pub fn new_continue(position: Position) -> Self {
+        Self {
+            kind: RowStmtKind::Continue,
+            position,
+        }
+    }
+
+    pub fn new_switch(condition_expr: RowExpr, run_stmt: RowStmt, position: Position) -> Self {
+        Self {
+            kind: RowStmtKind::Switch {
+                condition_expr,
+                run_stmt: Box::new(run_stmt),
+            },
+            position,
+        }
+    }
+
+    pub fn new_case(expr: RowExpr, stmt: RowStmt, position: Position) -> Self {
+        Self {
+            kind: RowStmtKind::Case {
+                expr,
+                stmt: Box::new(stmt),
+            },
+            position,
+        }
+    }
+
+    pub fn new_default(stmt: RowStmt, position: Position) -> Self {
+        Self {
+            kind: RowStmtKind::Default {
+                stmt: Box::new(stmt),
+            },
+            position,
+        }
+    }
+
+    pub fn new_goto(label: String, position: Position) -> Self {
+        Self {
+            kind: RowStmtKind::Goto { label },
+            position,
+        }
+    }
+
+    pub fn new_label(label: String, stmt: RowStmt, position: Position) -> Self {
+        Self {
+            kind: RowStmtKind::Label {
+                label,
+                stmt: Box::new(stmt),
+            },
+            position,
+        }
+    }
+
     pub fn new_cpd(stmts: Vec<RowStmt>, position: Position) -> Self {
         Self {
             kind: RowStmtKind::Cpd { stmts },
             position,
         }
     }
+
+    pub fn new_decl(decl: RowDecl, position: Position) -> Self {
+        Self {
+            kind: RowStmtKind::Decl { decl },
+            position,
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -98,7 +175,34 @@ pub enum RowStmtKind {
         condition_expr: RowExpr,
         run_stmt: Box<RowStmt>,
     },
+    DoWhile {
+        run_stmt: Box<RowStmt>,
+        condition_expr: RowExpr,
+    },
+    Break,
+    Continue,
+    Switch {
+        condition_expr: RowExpr,
+        run_stmt: Box<RowStmt>,
+    },
+    Case {
+        expr: RowExpr,
+        stmt: Box<RowStmt>,
+    },
+    Default {
+        stmt: Box<RowStmt>,
+    },
+    Goto {
+        label: String,
+    },
+    Label {
+        label: String,
+        stmt: Box<RowStmt>,
+    },
     Cpd {
         stmts: Vec<RowStmt>,
     },
+    Decl {
+        decl: RowDecl,
+    },
 }
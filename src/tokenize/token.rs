@@ -1,6 +1,6 @@
 use crate::file::position::Position;
 
-use super::token_kind::{PuncToken, TokenKind};
+use super::token_kind::{IntegerFormat, PuncToken, TokenKind};
 
 #[derive(Debug, Clone)]
 pub struct Token {
@@ -16,9 +16,9 @@ impl Token {
         }
     }
 
-    pub fn new_number(number: usize, position: Position) -> Self {
+    pub fn new_number(number: usize, format: IntegerFormat, position: Position) -> Self {
         Self {
-            kind: Box::new(TokenKind::Number(number)),
+            kind: Box::new(TokenKind::Number(number, format)),
             position,
         }
     }
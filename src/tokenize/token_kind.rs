@@ -1,17 +1,95 @@
 #[derive(Debug, Clone, PartialEq)]
 pub enum TokenKind {
     Ident(String),
-    Number(usize),
+    Number(usize, IntegerFormat),
+    FloatNumber(String, FloatSuffix),
+    Character(Encoding, i64),
+    /// The code units of a string literal without the terminating null character.
+    Str(Encoding, Vec<u32>),
     Punc(PuncToken),
     Return,
     If,
     Else,
     For,
     While,
+    Do,
+    Break,
+    Continue,
+    Switch,
+    Case,
+    Default,
+    Goto,
+    Void,
+    Char,
+    Short,
+    Int,
+    Long,
+    Float,
+    Double,
+    Signed,
+    Unsigned,
+    Struct,
+    Union,
+    Enum,
+    Typedef,
+    Static,
+    Extern,
+    Auto,
+    Const,
+    Volatile,
+    Sizeof,
+    Alignof,
 
     Eof,
 }
 
+/// The encoding prefix of a character constant or string literal.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Encoding {
+    /// no prefix
+    Char,
+    /// 'u8'
+    Utf8,
+    /// 'u'
+    Char16,
+    /// 'U'
+    Char32,
+    /// 'L'
+    Wide,
+}
+
+impl Encoding {
+    /// The size in bytes of a code unit.
+    pub fn unit_size(&self) -> usize {
+        match self {
+            Self::Char | Self::Utf8 => 1,
+            Self::Char16 => 2,
+            Self::Char32 | Self::Wide => 4,
+        }
+    }
+}
+
+/// How an integer constant is written, which decides its type together with its value.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct IntegerFormat {
+    pub is_decimal: bool,
+    /// 'u' 'U'
+    pub is_unsigned: bool,
+    /// 0 for no suffix, 1 for 'l' 'L' and 2 for 'll' 'LL'.
+    pub longs: usize,
+}
+
+/// The suffix of a floating constant, which decides its type.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FloatSuffix {
+    /// no suffix
+    Double,
+    /// 'f' 'F'
+    Float,
+    /// 'l' 'L'
+    LongDouble,
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum PuncToken {
     /// '[' '<:'
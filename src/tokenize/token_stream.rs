@@ -50,6 +50,13 @@ impl TokenStream {
         Err(anyhow!(format!("Failed to peek tokenstreem")))
     }
 
+    pub fn peek_nth(&self, n: usize) -> anyhow::Result<Token> {
+        if let Some(token) = self.tokens.get(n) {
+            return Ok(token.clone());
+        }
+        Err(anyhow!(format!("Failed to peek tokenstreem")))
+    }
+
     pub fn get_position(&self) -> anyhow::Result<Position> {
         let token = self.peek()?;
         Ok(token.position)
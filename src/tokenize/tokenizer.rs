@@ -2,17 +2,25 @@ use std::collections::VecDeque;
 
 use anyhow::anyhow;
 
-use crate::file::file_stream::FileStream;
+use crate::file::{file_stream::FileStream, position::Position};
 
 use super::{
     token::Token,
-    token_kind::{PuncToken, TokenKind},
+    token_kind::{Encoding, FloatSuffix, IntegerFormat, PuncToken, TokenKind},
 };
 
 pub struct Tokenizer {
     file_stream: FileStream,
 }
 
+/// One element of a character constant or string literal.
+enum CharElement {
+    /// A source character, simple escape or universal character name.
+    CodePoint(char),
+    /// A numeric escape giving the value of a code unit directly.
+    Unit { value: u32, position: Position },
+}
+
 impl Tokenizer {
     pub fn new(file_stream: FileStream) -> Self {
         Self { file_stream }
@@ -27,12 +35,23 @@ impl Tokenizer {
                 continue;
             }
 
+            // A number may start with `.`, so it is tried before punctuators.
+            if let Some(token) = self.tokenize_number()? {
+                tokens.push_back(token);
+                continue;
+            }
+
             if let Some(token) = self.tokenize_symbol() {
                 tokens.push_back(token);
                 continue;
             }
 
-            if let Some(token) = self.tokenize_number() {
+            if let Some(token) = self.tokenize_string()? {
+                tokens.push_back(token);
+                continue;
+            }
+
+            if let Some(token) = self.tokenize_character()? {
                 tokens.push_back(token);
                 continue;
             }
@@ -121,19 +140,373 @@ impl Tokenizer {
         }
     }
 
-    fn tokenize_number(&mut self) -> Option<Token> {
-        if self.file_stream.starts_with_number() {
-            let (position, char) = self.file_stream.advance(1).unwrap();
-            let mut number = String::from(char);
-            while self.file_stream.starts_with_number() {
-                let (_, char) = self.file_stream.advance(1).unwrap();
-                number.push(char);
+    fn tokenize_number(&mut self) -> anyhow::Result<Option<Token>> {
+        if !self.file_stream.starts_with_numeric_constant() {
+            return Ok(None);
+        }
+        // Read the whole preprocessing number, so that a bad suffix is an error.
+        let (position, char) = self.file_stream.advance(1).unwrap();
+        let mut literal = String::from(char);
+        while let Some((_, char)) = self.file_stream.peek() {
+            let is_exponent_sign =
+                matches!(char, '+' | '-') && literal.ends_with(['e', 'E', 'p', 'P']);
+            if !(char.is_ascii_alphanumeric() || matches!(char, '_' | '.') || is_exponent_sign) {
+                break;
             }
-            let number = number.parse::<usize>().unwrap();
-            Some(Token::new_number(number, position))
+            self.file_stream.advance(1);
+            literal.push(char);
+        }
+
+        let lower = literal.to_ascii_lowercase();
+        let is_hex = lower.starts_with("0x");
+        if lower.contains('.') || lower.contains(if is_hex { 'p' } else { 'e' }) {
+            let (literal, suffix) = Self::float_constant(&literal, &position)?;
+            return Ok(Some(Token::new(
+                TokenKind::FloatNumber(literal, suffix),
+                position,
+            )));
+        }
+        let (radix, body) = if let Some(body) = lower.strip_prefix("0x") {
+            (16, body)
+        } else if let Some(body) = lower.strip_prefix("0b") {
+            (2, body)
+        } else if lower.starts_with('0') {
+            (8, &lower[..])
         } else {
-            None
+            (10, &lower[..])
+        };
+        let digits_len = body
+            .find(|c: char| !c.is_digit(radix.max(10)))
+            .unwrap_or(body.len());
+        let (digits, suffix) = body.split_at(digits_len);
+        let suffix = &literal[literal.len() - suffix.len()..];
+        if digits.is_empty() {
+            return Err(anyhow!(
+                "{}Invalid suffix `{}` on integer constant",
+                position,
+                &literal[1..]
+            ));
+        }
+        if let Some(digit) = digits.chars().find(|c| !c.is_digit(radix)) {
+            let base = if radix == 8 { "octal" } else { "binary" };
+            return Err(anyhow!(
+                "{}Invalid digit `{}` in {} constant",
+                position,
+                digit,
+                base
+            ));
+        }
+
+        let Some((is_unsigned, longs)) = Self::integer_suffix(suffix) else {
+            return Err(anyhow!(
+                "{}Invalid suffix `{}` on integer constant",
+                position,
+                suffix
+            ));
+        };
+        let Ok(number) = u64::from_str_radix(digits, radix) else {
+            return Err(anyhow!(
+                "{}Integer constant is too large for its type",
+                position
+            ));
+        };
+        let format = IntegerFormat {
+            is_decimal: radix == 10,
+            is_unsigned,
+            longs,
+        };
+        Ok(Some(Token::new_number(number as usize, format, position)))
+    }
+
+    /// Checks a decimal or hexadecimal floating constant and splits it into its digits and exponent,
+    /// which are kept as written so that they can be rounded to the precision of their type, and
+    /// its suffix.
+    fn float_constant(literal: &str, position: &Position) -> anyhow::Result<(String, FloatSuffix)> {
+        let lower = literal.to_ascii_lowercase();
+        let (radix, exponent_char, body) = match lower.strip_prefix("0x") {
+            Some(body) => (16, 'p', body),
+            None => (10, 'e', &lower[..]),
+        };
+        let mantissa_len = body
+            .find(|c: char| !(c.is_digit(radix) || c == '.'))
+            .unwrap_or(body.len());
+        let (mantissa, rest) = body.split_at(mantissa_len);
+        if mantissa.matches('.').count() > 1 {
+            return Err(anyhow!("{}Too many decimal points in number", position));
         }
+        if mantissa == "." {
+            return Err(anyhow!("{}No digits in floating constant", position));
+        }
+        let suffix = match rest.strip_prefix(exponent_char) {
+            Some(rest) => {
+                let sign_len = rest.starts_with(['+', '-']) as usize;
+                let digits_len = rest[sign_len..]
+                    .find(|c: char| !c.is_ascii_digit())
+                    .unwrap_or(rest.len() - sign_len);
+                if digits_len == 0 {
+                    return Err(anyhow!("{}Exponent has no digits", position));
+                }
+                &rest[sign_len + digits_len..]
+            }
+            None if radix == 16 => {
+                return Err(anyhow!(
+                    "{}Hexadecimal floating constant requires an exponent",
+                    position
+                ));
+            }
+            None => rest,
+        };
+        let digits = literal[..literal.len() - suffix.len()].to_string();
+        let suffix = match suffix {
+            "" => FloatSuffix::Double,
+            "f" => FloatSuffix::Float,
+            "l" => FloatSuffix::LongDouble,
+            _ => {
+                return Err(anyhow!(
+                    "{}Invalid suffix `{}` on floating constant",
+                    position,
+                    &literal[literal.len() - suffix.len()..]
+                ));
+            }
+        };
+        Ok((digits, suffix))
+    }
+
+    /// Parses the suffix of an integer constant into whether it is unsigned and its number of
+    /// 'l's. The two 'l's of 'll' must have the same case.
+    fn integer_suffix(suffix: &str) -> Option<(bool, usize)> {
+        let (is_unsigned, long) = match suffix.strip_prefix(['u', 'U']) {
+            Some(long) => (true, long),
+            None => match suffix.strip_suffix(['u', 'U']) {
+                Some(long) => (true, long),
+                None => (false, suffix),
+            },
+        };
+        let longs = match long {
+            "" => 0,
+            "l" | "L" => 1,
+            "ll" | "LL" => 2,
+            _ => return None,
+        };
+        Some((is_unsigned, longs))
+    }
+
+    fn tokenize_string(&mut self) -> anyhow::Result<Option<Token>> {
+        let Some((mut encoding, prefix_len)) = self.literal_prefix('"') else {
+            return Ok(None);
+        };
+        let (position, _) = self.file_stream.advance(prefix_len + 1).unwrap();
+        let mut elements = self.read_char_elements('"', &position)?;
+        // Adjacent string literals are concatenated, taking the prefix of any prefixed piece.
+        loop {
+            while self.file_stream.starts_with_white_space() {
+                self.file_stream.advance(1);
+            }
+            let Some((next, prefix_len)) = self.literal_prefix('"') else {
+                break;
+            };
+            let (next_position, _) = self.file_stream.advance(prefix_len + 1).unwrap();
+            encoding = match (encoding, next) {
+                (encoding, Encoding::Char) => encoding,
+                (Encoding::Char, next) => next,
+                (encoding, next) if encoding == next => encoding,
+                _ => {
+                    return Err(anyhow!(
+                        "{}Concatenation of string literals with different prefixes",
+                        next_position
+                    ));
+                }
+            };
+            elements.extend(self.read_char_elements('"', &next_position)?);
+        }
+        let units = Self::encode(encoding, elements)?;
+        Ok(Some(Token::new(TokenKind::Str(encoding, units), position)))
+    }
+
+    fn tokenize_character(&mut self) -> anyhow::Result<Option<Token>> {
+        let Some((encoding, prefix_len)) = self.literal_prefix('\'') else {
+            return Ok(None);
+        };
+        let (position, _) = self.file_stream.advance(prefix_len + 1).unwrap();
+        let elements = self.read_char_elements('\'', &position)?;
+        let units = Self::encode(encoding, elements)?;
+        let value = match (encoding, units.as_slice()) {
+            (_, []) => return Err(anyhow!("{}Empty character constant", position)),
+            // A plain character has type `int` but the value of a `char`.
+            (Encoding::Char, [unit]) => *unit as u8 as i8 as i64,
+            (Encoding::Char, units) => {
+                eprintln!("{}Warning: multi-character character constant", position);
+                units
+                    .iter()
+                    .fold(0i32, |value, unit| value.wrapping_shl(8) | *unit as i32)
+                    as i64
+            }
+            (Encoding::Wide, [unit]) => *unit as i32 as i64,
+            (_, [unit]) => *unit as i64,
+            _ => {
+                return Err(anyhow!(
+                    "{}Character constant too long for its type",
+                    position
+                ));
+            }
+        };
+        Ok(Some(Token::new(
+            TokenKind::Character(encoding, value),
+            position,
+        )))
+    }
+
+    /// Detects a character constant or string literal opened by `quote`, returning its encoding
+    /// and the length of its prefix.
+    fn literal_prefix(&self, quote: char) -> Option<(Encoding, usize)> {
+        [
+            ("", Encoding::Char),
+            ("u8", Encoding::Utf8),
+            ("u", Encoding::Char16),
+            ("U", Encoding::Char32),
+            ("L", Encoding::Wide),
+        ]
+        .into_iter()
+        .find(|(prefix, _)| {
+            self.file_stream
+                .starts_with(&format!("{}{}", prefix, quote))
+        })
+        .map(|(prefix, encoding)| (encoding, prefix.len()))
+    }
+
+    /// Reads the elements up to the closing `quote`.
+    fn read_char_elements(
+        &mut self,
+        quote: char,
+        position: &Position,
+    ) -> anyhow::Result<Vec<CharElement>> {
+        let mut elements = Vec::new();
+        while !self.file_stream.starts_with(&quote.to_string()) {
+            if self.file_stream.is_empty() || self.file_stream.starts_with("\n") {
+                return Err(anyhow!(
+                    "{}Missing terminating {} character",
+                    position,
+                    quote
+                ));
+            }
+            elements.push(self.read_char_element()?);
+        }
+        self.file_stream.advance(1);
+        Ok(elements)
+    }
+
+    /// Transcodes characters to UTF-8, UTF-16 or UTF-32 code units.
+    fn encode(encoding: Encoding, elements: Vec<CharElement>) -> anyhow::Result<Vec<u32>> {
+        let mut units = Vec::new();
+        for element in elements.into_iter() {
+            match element {
+                CharElement::CodePoint(char) => match encoding {
+                    Encoding::Char | Encoding::Utf8 => units.extend(
+                        char.encode_utf8(&mut [0; 4])
+                            .bytes()
+                            .map(|byte| byte as u32),
+                    ),
+                    Encoding::Char16 => units.extend(
+                        char.encode_utf16(&mut [0; 2])
+                            .iter()
+                            .map(|unit| *unit as u32),
+                    ),
+                    Encoding::Char32 | Encoding::Wide => units.push(char as u32),
+                },
+                CharElement::Unit { value, position } => {
+                    if (value as u64) >> (encoding.unit_size() * 8) != 0 {
+                        return Err(anyhow!("{}Escape sequence out of range", position));
+                    }
+                    units.push(value);
+                }
+            }
+        }
+        Ok(units)
+    }
+
+    fn read_char_element(&mut self) -> anyhow::Result<CharElement> {
+        let (position, char) = self.file_stream.advance(1).unwrap();
+        if char != '\\' {
+            return Ok(CharElement::CodePoint(char));
+        }
+        let Some((_, char)) = self
+            .file_stream
+            .peek()
+            .filter(|(_, char)| !self.file_stream.is_empty() && *char != '\n')
+        else {
+            return Err(anyhow!("{}Incomplete escape sequence", position));
+        };
+        self.file_stream.advance(1);
+        let char = match char {
+            '\'' | '"' | '?' | '\\' => char,
+            'a' => '\x07',
+            'b' => '\x08',
+            'f' => '\x0c',
+            'n' => '\n',
+            'r' => '\r',
+            't' => '\t',
+            'v' => '\x0b',
+            '0'..='7' => {
+                let mut value = char.to_digit(8).unwrap();
+                for _ in 0..2 {
+                    match self.file_stream.peek() {
+                        Some((_, digit @ '0'..='7')) => {
+                            self.file_stream.advance(1);
+                            value = value * 8 + digit.to_digit(8).unwrap();
+                        }
+                        _ => break,
+                    }
+                }
+                return Ok(CharElement::Unit { value, position });
+            }
+            'x' => {
+                let mut value: Option<u32> = None;
+                while let Some(digit) = self.peek_digit(16) {
+                    self.file_stream.advance(1);
+                    value = Some(
+                        value
+                            .unwrap_or(0)
+                            .checked_mul(16)
+                            .and_then(|value| value.checked_add(digit))
+                            .ok_or_else(|| {
+                                anyhow!("{}Hex escape sequence out of range", position)
+                            })?,
+                    );
+                }
+                let Some(value) = value else {
+                    return Err(anyhow!("{}\\x used with no following hex digits", position));
+                };
+                return Ok(CharElement::Unit { value, position });
+            }
+            'u' | 'U' => {
+                let len = if char == 'u' { 4 } else { 8 };
+                let mut value = 0;
+                for _ in 0..len {
+                    let Some(digit) = self.peek_digit(16) else {
+                        return Err(anyhow!("{}Incomplete universal character name", position));
+                    };
+                    self.file_stream.advance(1);
+                    value = value * 16 + digit;
+                }
+                // Only `$`, `@` and `` ` `` may be named below U+00A0.
+                return match char::from_u32(value) {
+                    Some(char) if value >= 0xa0 || matches!(char, '$' | '@' | '`') => {
+                        Ok(CharElement::CodePoint(char))
+                    }
+                    _ => Err(anyhow!("{}Invalid universal character name", position)),
+                };
+            }
+            _ => {
+                return Err(anyhow!("{}Unknown escape sequence `\\{}`", position, char));
+            }
+        };
+        Ok(CharElement::CodePoint(char))
+    }
+
+    fn peek_digit(&self, radix: u32) -> Option<u32> {
+        self.file_stream
+            .peek()
+            .and_then(|(_, char)| char.to_digit(radix))
     }
 
     fn tokenize_other(&mut self) -> Option<Token> {
@@ -153,6 +526,33 @@ impl Tokenizer {
                 "else" => TokenKind::Else,
                 "for" => TokenKind::For,
                 "while" => TokenKind::While,
+                "do" => TokenKind::Do,
+                "break" => TokenKind::Break,
+                "continue" => TokenKind::Continue,
+                "switch" => TokenKind::Switch,
+                "case" => TokenKind::Case,
+                "default" => TokenKind::Default,
+                "goto" => TokenKind::Goto,
+                "void" => TokenKind::Void,
+                "char" => TokenKind::Char,
+                "short" => TokenKind::Short,
+                "int" => TokenKind::Int,
+                "long" => TokenKind::Long,
+                "float" => TokenKind::Float,
+                "double" => TokenKind::Double,
+                "signed" => TokenKind::Signed,
+                "unsigned" => TokenKind::Unsigned,
+                "struct" => TokenKind::Struct,
+                "union" => TokenKind::Union,
+                "enum" => TokenKind::Enum,
+                "typedef" => TokenKind::Typedef,
+                "static" => TokenKind::Static,
+                "extern" => TokenKind::Extern,
+                "auto" => TokenKind::Auto,
+                "const" => TokenKind::Const,
+                "volatile" => TokenKind::Volatile,
+                "sizeof" => TokenKind::Sizeof,
+                "_Alignof" => TokenKind::Alignof,
                 _ => TokenKind::Ident(ident),
             };
             Some(Token::new(kind, position))